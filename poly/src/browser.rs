@@ -4,10 +4,22 @@ pub mod event;
 pub mod file;
 pub mod keyboard;
 pub mod mouse;
+pub mod pointer;
 pub mod selector;
 pub mod subscription;
+pub mod touch;
 pub mod value;
 
+use std::time::Duration;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum RateLimit {
+    Debounce(DebounceConfig),
+    Throttle(ThrottleConfig),
+}
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DebounceConfig {
@@ -16,9 +28,64 @@ pub struct DebounceConfig {
     trailing: bool,
 }
 
+impl DebounceConfig {
+    pub fn new(delay: Duration) -> DebounceConfig {
+        DebounceConfig {
+            delay: delay.as_millis() as u32,
+            leading: false,
+            trailing: true,
+        }
+    }
+
+    pub fn leading(mut self, leading: bool) -> DebounceConfig {
+        self.leading = leading;
+        self
+    }
+
+    pub fn trailing(mut self, trailing: bool) -> DebounceConfig {
+        self.trailing = trailing;
+        self
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleConfig {
+    interval: u32,
+    leading: bool,
+    trailing: bool,
+}
+
+impl ThrottleConfig {
+    pub fn new(interval: Duration) -> ThrottleConfig {
+        ThrottleConfig {
+            interval: interval.as_millis() as u32,
+            leading: true,
+            trailing: true,
+        }
+    }
+
+    pub fn leading(mut self, leading: bool) -> ThrottleConfig {
+        self.leading = leading;
+        self
+    }
+
+    pub fn trailing(mut self, trailing: bool) -> ThrottleConfig {
+        self.trailing = trailing;
+        self
+    }
+}
+
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WindowSize {
     pub width: u32,
     pub height: u32,
 }
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollPosition {
+    pub top: f64,
+    pub left: f64,
+}
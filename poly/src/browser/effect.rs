@@ -3,20 +3,24 @@ pub mod clipboard;
 pub mod console;
 pub mod dom;
 pub mod effectful_msg;
+pub mod http;
 pub mod local_storage;
 pub mod navigation;
 pub mod session_storage;
 pub mod time;
+pub mod web_socket;
 
 use crate::browser::effect::browser::Browser;
 use crate::browser::effect::clipboard::Clipboard;
 use crate::browser::effect::console::Console;
 use crate::browser::effect::dom::Dom;
 use crate::browser::effect::effectful_msg::EffectfulMsg;
+use crate::browser::effect::http::Http;
 use crate::browser::effect::local_storage::LocalStorage;
 use crate::browser::effect::navigation::Navigation;
 use crate::browser::effect::session_storage::SessionStorage;
 use crate::browser::effect::time::Time;
+use crate::browser::effect::web_socket::WebSocket;
 use serde_json::json;
 
 #[derive(Clone, serde::Serialize)]
@@ -33,6 +37,8 @@ pub enum Effect<Msg> {
     Console(Console),
     Clipboard(Clipboard),
     Browser(Browser),
+    Http(Http),
+    WebSocket(WebSocket),
     Custom(serde_json::Value),
     Batch(Vec<Effect<Msg>>),
 }
@@ -45,6 +51,39 @@ impl<Msg> Effect<Msg> {
             _ => vec![self],
         }
     }
+
+    pub fn map<ToMsg, B>(self, to_msg: ToMsg) -> Effect<B>
+    where
+        ToMsg: Fn(Msg) -> B,
+    {
+        self.map_ref(&to_msg)
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> Effect<B> {
+        match self {
+            Effect::None => Effect::None,
+            Effect::EffectfulMsg(effectful_msg) => {
+                Effect::EffectfulMsg(Box::new(effectful_msg.map_ref(to_msg)))
+            }
+            Effect::Dom(dom) => Effect::Dom(dom),
+            Effect::Time(time) => Effect::Time(time),
+            Effect::Navigation(navigation) => Effect::Navigation(navigation),
+            Effect::LocalStorage(local_storage) => Effect::LocalStorage(local_storage),
+            Effect::SessionStorage(session_storage) => Effect::SessionStorage(session_storage),
+            Effect::Console(console) => Effect::Console(console),
+            Effect::Clipboard(clipboard) => Effect::Clipboard(clipboard),
+            Effect::Browser(browser) => Effect::Browser(browser),
+            Effect::Http(http) => Effect::Http(http),
+            Effect::WebSocket(web_socket) => Effect::WebSocket(web_socket),
+            Effect::Custom(value) => Effect::Custom(value),
+            Effect::Batch(effects) => Effect::Batch(
+                effects
+                    .into_iter()
+                    .map(|effect| effect.map_ref(to_msg))
+                    .collect(),
+            ),
+        }
+    }
 }
 
 pub fn none<Msg>() -> Effect<Msg> {
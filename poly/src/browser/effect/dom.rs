@@ -1,7 +1,9 @@
 use crate::browser::dom_id::DomId;
 use crate::browser::effect::Effect;
+use crate::browser::event::EventField;
 use crate::browser::event::EventTarget;
 use crate::browser::selector::Selector;
+use crate::head::Meta;
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type", content = "config")]
@@ -35,6 +37,18 @@ pub enum Dom {
         element_id: String,
     },
     GetWindowSize,
+    GetMousePosition,
+    GetPointerInfo,
+    GetWheelDelta,
+    GetScrollPosition,
+    GetDroppedFiles,
+    GetTouches,
+    GetGesture,
+    GetIntersectionEntry,
+    #[serde(rename_all = "camelCase")]
+    GetEventData {
+        fields: Vec<EventField>,
+    },
     #[serde(rename_all = "camelCase")]
     DispatchEvent {
         event_target: EventTarget,
@@ -42,6 +56,11 @@ pub enum Dom {
         bubbles: bool,
         cancelable: bool,
     },
+    #[serde(rename_all = "camelCase")]
+    SetTitle {
+        title: String,
+    },
+    SetMeta(Meta),
 }
 
 pub fn focus_element<Msg, Id>(id: Id) -> Effect<Msg>
@@ -123,6 +142,45 @@ pub fn window_size<Msg>() -> Effect<Msg> {
     Effect::Dom(Dom::GetWindowSize)
 }
 
+// These read the event that triggered the listener, so only event listeners can use them
+pub(crate) fn get_mouse_position<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetMousePosition)
+}
+
+pub(crate) fn get_pointer_info<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetPointerInfo)
+}
+
+pub(crate) fn get_wheel_delta<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetWheelDelta)
+}
+
+pub(crate) fn get_scroll_position<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetScrollPosition)
+}
+
+pub(crate) fn get_dropped_files<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetDroppedFiles)
+}
+
+pub(crate) fn get_touches<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetTouches)
+}
+
+pub(crate) fn get_gesture<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetGesture)
+}
+
+pub(crate) fn get_intersection_entry<Msg>() -> Effect<Msg> {
+    Effect::Dom(Dom::GetIntersectionEntry)
+}
+
+pub(crate) fn get_event_data<Msg>(fields: &[EventField]) -> Effect<Msg> {
+    Effect::Dom(Dom::GetEventData {
+        fields: fields.to_vec(),
+    })
+}
+
 pub fn dispatch_window_event<Msg>(event_type: &str) -> Effect<Msg> {
     Effect::Dom(Dom::DispatchEvent {
         event_target: EventTarget::Window,
@@ -151,3 +209,17 @@ pub fn dispatch_element_event<Msg>(id: impl DomId, event_type: &str) -> Effect<M
         cancelable: false,
     })
 }
+
+pub fn set_title<Msg>(title: &str) -> Effect<Msg> {
+    Effect::Dom(Dom::SetTitle {
+        title: title.to_string(),
+    })
+}
+
+pub fn set_meta<Msg>(name: &str, content: &str) -> Effect<Msg> {
+    Effect::Dom(Dom::SetMeta(Meta::name(name, content)))
+}
+
+pub fn set_meta_property<Msg>(property: &str, content: &str) -> Effect<Msg> {
+    Effect::Dom(Dom::SetMeta(Meta::property(property, content)))
+}
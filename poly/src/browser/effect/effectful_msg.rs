@@ -7,6 +7,22 @@ pub struct EffectfulMsg<Msg> {
     pub effect: Effect<Msg>,
 }
 
+impl<Msg> EffectfulMsg<Msg> {
+    pub fn map<ToMsg, B>(self, to_msg: ToMsg) -> EffectfulMsg<B>
+    where
+        ToMsg: Fn(Msg) -> B,
+    {
+        self.map_ref(&to_msg)
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> EffectfulMsg<B> {
+        EffectfulMsg {
+            msg: to_msg(self.msg),
+            effect: self.effect.map_ref(to_msg),
+        }
+    }
+}
+
 pub fn effectful_msg<Msg>(msg: Msg, effect: Effect<Msg>) -> Effect<Msg> {
     Effect::EffectfulMsg(Box::new(EffectfulMsg { msg, effect }))
 }
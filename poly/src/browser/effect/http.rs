@@ -0,0 +1,286 @@
+use crate::browser;
+use crate::browser::effect::effectful_msg::effectful_msg;
+use crate::browser::effect::Effect;
+use crate::browser::value::Capture;
+use crate::browser::value::Value;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum Http {
+    SendRequest(Request),
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Body,
+    pub expect: Expect,
+    pub timeout: Option<u64>,
+    pub credentials: Credentials,
+}
+
+impl Request {
+    pub fn new(method: Method, url: &str) -> Request {
+        Request {
+            method,
+            url: url.to_string(),
+            headers: BTreeMap::new(),
+            body: Body::Empty,
+            expect: Expect::Json,
+            timeout: None,
+            credentials: Credentials::SameOrigin,
+        }
+    }
+
+    pub fn get(url: &str) -> Request {
+        Request::new(Method::Get, url)
+    }
+
+    pub fn post(url: &str) -> Request {
+        Request::new(Method::Post, url)
+    }
+
+    pub fn put(url: &str) -> Request {
+        Request::new(Method::Put, url)
+    }
+
+    pub fn delete(url: &str) -> Request {
+        Request::new(Method::Delete, url)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Request {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn json_body<V>(mut self, value: V) -> Request
+    where
+        V: serde::Serialize,
+    {
+        self.body = Body::Json(browser::value::to_value(value));
+        self
+    }
+
+    pub fn text_body(mut self, text: &str) -> Request {
+        self.body = Body::Text(text.to_string());
+        self
+    }
+
+    pub fn expect_json(mut self) -> Request {
+        self.expect = Expect::Json;
+        self
+    }
+
+    pub fn expect_text(mut self) -> Request {
+        self.expect = Expect::Text;
+        self
+    }
+
+    pub fn timeout(mut self, duration: Duration) -> Request {
+        self.timeout = Some(duration.as_millis() as u64);
+        self
+    }
+
+    pub fn credentials(mut self, credentials: Credentials) -> Request {
+        self.credentials = credentials;
+        self
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum Body {
+    Empty,
+    Json(Value),
+    Text(String),
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Expect {
+    Json,
+    Text,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Credentials {
+    Omit,
+    SameOrigin,
+    Include,
+}
+
+pub fn send<Msg, ToMsg, T>(request: Request, to_msg: ToMsg) -> Effect<Msg>
+where
+    ToMsg: Fn(Capture<HttpResponse<T>>) -> Msg,
+{
+    let msg = to_msg(Default::default());
+    let effect = Effect::Http(Http::SendRequest(request));
+
+    effectful_msg(msg, effect)
+}
+
+pub fn get<Msg, ToMsg, T>(url: &str, to_msg: ToMsg) -> Effect<Msg>
+where
+    ToMsg: Fn(Capture<HttpResponse<T>>) -> Msg,
+{
+    send(Request::get(url), to_msg)
+}
+
+pub fn post_json<Msg, V, ToMsg, T>(url: &str, value: V, to_msg: ToMsg) -> Effect<Msg>
+where
+    V: serde::Serialize,
+    ToMsg: Fn(Capture<HttpResponse<T>>) -> Msg,
+{
+    send(Request::post(url).json_body(value), to_msg)
+}
+
+pub fn put_json<Msg, V, ToMsg, T>(url: &str, value: V, to_msg: ToMsg) -> Effect<Msg>
+where
+    V: serde::Serialize,
+    ToMsg: Fn(Capture<HttpResponse<T>>) -> Msg,
+{
+    send(Request::put(url).json_body(value), to_msg)
+}
+
+pub fn delete<Msg, ToMsg, T>(url: &str, to_msg: ToMsg) -> Effect<Msg>
+where
+    ToMsg: Fn(Capture<HttpResponse<T>>) -> Msg,
+{
+    send(Request::delete(url), to_msg)
+}
+
+#[derive(Debug, Clone)]
+pub enum HttpResponse<T> {
+    Ok(Response<T>),
+    Err(HttpError),
+}
+
+impl<T> HttpResponse<T> {
+    pub fn into_result(self) -> Result<Response<T>, HttpError> {
+        match self {
+            HttpResponse::Ok(response) => Ok(response),
+            HttpResponse::Err(err) => Err(err),
+        }
+    }
+}
+
+impl<T> Default for HttpResponse<T> {
+    fn default() -> Self {
+        HttpResponse::Err(HttpError::Network {
+            message: "Request has not been sent".to_string(),
+        })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for HttpResponse<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let response = match RawResponse::deserialize(deserializer)? {
+            RawResponse::Ok {
+                status,
+                headers,
+                body,
+            } => match serde_json::from_value(body.clone()) {
+                Ok(body) => HttpResponse::Ok(Response {
+                    status,
+                    headers,
+                    body,
+                }),
+
+                Err(err) => HttpResponse::Err(HttpError::Decode {
+                    status,
+                    headers,
+                    body: body.to_string(),
+                    message: err.to_string(),
+                }),
+            },
+
+            RawResponse::Err(err) => HttpResponse::Err(err),
+        };
+
+        Ok(response)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    pub body: T,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum HttpError {
+    #[serde(rename_all = "camelCase")]
+    Network {
+        message: String,
+    },
+    Timeout,
+    #[serde(rename_all = "camelCase")]
+    BadStatus {
+        status: u16,
+        headers: BTreeMap<String, String>,
+        body: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Decode {
+        status: u16,
+        headers: BTreeMap<String, String>,
+        body: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::Network { message } => write!(f, "Network error: {}", message),
+            HttpError::Timeout => write!(f, "Request timed out"),
+            HttpError::BadStatus { status, .. } => write!(f, "Bad status: {}", status),
+            HttpError::Decode { message, .. } => {
+                write!(f, "Failed to decode response: {}", message)
+            }
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+enum RawResponse {
+    #[serde(rename_all = "camelCase")]
+    Ok {
+        status: u16,
+        headers: BTreeMap<String, String>,
+        body: serde_json::Value,
+    },
+    Err(HttpError),
+}
@@ -7,6 +7,8 @@ pub enum Navigation {
     PushUrl(String),
     ReplaceUrl(String),
     SetLocation(String),
+    GetCurrentUrl,
+    FollowLink,
 }
 
 pub fn push_url<Msg>(url: &str) -> Effect<Msg> {
@@ -20,3 +22,11 @@ pub fn replace_url<Msg>(url: &str) -> Effect<Msg> {
 pub fn set_location<Msg>(url: &str) -> Effect<Msg> {
     Effect::Navigation(Navigation::SetLocation(url.to_string()))
 }
+
+pub fn get_current_url<Msg>() -> Effect<Msg> {
+    Effect::Navigation(Navigation::GetCurrentUrl)
+}
+
+pub fn follow_link<Msg>() -> Effect<Msg> {
+    Effect::Navigation(Navigation::FollowLink)
+}
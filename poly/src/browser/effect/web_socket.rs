@@ -0,0 +1,58 @@
+use crate::browser;
+use crate::browser::effect::Effect;
+use crate::browser::value::Value;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum WebSocket {
+    #[serde(rename_all = "camelCase")]
+    Send { socket_id: String, frame: Frame },
+    #[serde(rename_all = "camelCase")]
+    Close {
+        socket_id: String,
+        code: Option<u16>,
+        reason: Option<String>,
+    },
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum Frame {
+    Text(String),
+    Json(Value),
+}
+
+pub fn send_text<Msg>(socket_id: &str, text: &str) -> Effect<Msg> {
+    Effect::WebSocket(WebSocket::Send {
+        socket_id: socket_id.to_string(),
+        frame: Frame::Text(text.to_string()),
+    })
+}
+
+pub fn send_json<Msg, V>(socket_id: &str, value: V) -> Effect<Msg>
+where
+    V: serde::Serialize,
+{
+    Effect::WebSocket(WebSocket::Send {
+        socket_id: socket_id.to_string(),
+        frame: Frame::Json(browser::value::to_value(value)),
+    })
+}
+
+pub fn close<Msg>(socket_id: &str) -> Effect<Msg> {
+    Effect::WebSocket(WebSocket::Close {
+        socket_id: socket_id.to_string(),
+        code: None,
+        reason: None,
+    })
+}
+
+pub fn close_with_reason<Msg>(socket_id: &str, code: u16, reason: &str) -> Effect<Msg> {
+    Effect::WebSocket(WebSocket::Close {
+        socket_id: socket_id.to_string(),
+        code: Some(code),
+        reason: Some(reason.to_string()),
+    })
+}
@@ -1,3 +1,6 @@
+use crate::browser::keyboard::Modifiers;
+use std::collections::BTreeMap;
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type", content = "config")]
 #[serde(rename_all = "camelCase")]
@@ -9,3 +12,72 @@ pub enum EventTarget {
         element_id: String,
     },
 }
+
+#[derive(Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EventField {
+    Key,
+    Code,
+    Modifiers,
+    ClientPosition,
+    PagePosition,
+    TargetId,
+    TargetData,
+    SelectionStart,
+    TimeStamp,
+}
+
+impl EventField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventField::Key => "key",
+            EventField::Code => "code",
+            EventField::Modifiers => "modifiers",
+            EventField::ClientPosition => "clientPosition",
+            EventField::PagePosition => "pagePosition",
+            EventField::TargetId => "targetId",
+            EventField::TargetData => "targetData",
+            EventField::SelectionStart => "selectionStart",
+            EventField::TimeStamp => "timeStamp",
+        }
+    }
+
+    pub fn all() -> Vec<EventField> {
+        vec![
+            EventField::Key,
+            EventField::Code,
+            EventField::Modifiers,
+            EventField::ClientPosition,
+            EventField::PagePosition,
+            EventField::TargetId,
+            EventField::TargetData,
+            EventField::SelectionStart,
+            EventField::TimeStamp,
+        ]
+    }
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventData {
+    pub key: Option<String>,
+    pub code: Option<String>,
+    pub modifiers: Option<Modifiers>,
+    pub client_x: Option<f64>,
+    pub client_y: Option<f64>,
+    pub page_x: Option<f64>,
+    pub page_y: Option<f64>,
+    pub target_id: Option<String>,
+    pub target_data: Option<BTreeMap<String, String>>,
+    pub selection_start: Option<u32>,
+    pub time_stamp: Option<f64>,
+}
+
+impl EventData {
+    pub fn data(&self, name: &str) -> Option<&str> {
+        self.target_data
+            .as_ref()
+            .and_then(|data| data.get(name))
+            .map(String::as_str)
+    }
+}
@@ -3,62 +3,323 @@ use serde::Serialize;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone)]
-pub enum Key {
-    Any,
-    Escape,
-    Enter,
-    Key(String),
-}
+macro_rules! named_keys {
+    ($name:ident { $($variant:ident),* $(,)? } { $($extra:tt)* }) => {
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        #[allow(clippy::upper_case_acronyms)]
+        pub enum $name {
+            $($variant,)*
+            $($extra)*
+        }
 
-impl Serialize for Key {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::ser::Serializer,
-    {
-        serializer.serialize_str(&self.to_string())
-    }
+        impl $name {
+            fn name(&self) -> Option<&'static str> {
+                match self {
+                    $($name::$variant => Some(stringify!($variant)),)*
+                    _ => None,
+                }
+            }
+
+            fn from_name(s: &str) -> Option<$name> {
+                $(
+                    if s.eq_ignore_ascii_case(stringify!($variant)) {
+                        return Some($name::$variant);
+                    }
+                )*
+
+                None
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::ser::Serializer,
+            {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: serde::de::Deserializer<'de>,
+            {
+                let s = String::deserialize(deserializer)?;
+                $name::from_str(&s).map_err(serde::de::Error::custom)
+            }
+        }
+    };
 }
 
-impl<'de> Deserialize<'de> for Key {
-    fn deserialize<D>(deserializer: D) -> Result<Key, D::Error>
-    where
-        D: serde::de::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Key::from_str(&s).map_err(serde::de::Error::custom)
+named_keys!(Key {
+    // Modifier keys
+    Alt, AltGraph, CapsLock, Control, Fn, FnLock, Hyper, Meta, NumLock, ScrollLock, Shift, Super,
+    Symbol, SymbolLock,
+    // Whitespace keys
+    Enter, Tab, Space,
+    // Navigation keys
+    ArrowDown, ArrowLeft, ArrowRight, ArrowUp, End, Home, PageDown, PageUp,
+    // Editing keys
+    Backspace, Clear, Copy, CrSel, Cut, Delete, EraseEof, ExSel, Insert, Paste, Redo, Undo,
+    // UI keys
+    Accept, Again, Attn, Cancel, ContextMenu, Escape, Execute, Find, Finish, Help, Pause, Play,
+    Props, Select, ZoomIn, ZoomOut,
+    // Device keys
+    BrightnessDown, BrightnessUp, Eject, LogOff, Power, PowerOff, PrintScreen, Hibernate,
+    Standby, WakeUp,
+    // IME and composition keys
+    AllCandidates, Alphanumeric, CodeInput, Compose, Convert, Dead, FinalMode, GroupFirst,
+    GroupLast, GroupNext, GroupPrevious, ModeChange, NextCandidate, NonConvert,
+    PreviousCandidate, Process, SingleCandidate, HangulMode, HanjaMode, JunjaMode, Eisu,
+    Hankaku, Hiragana, HiraganaKatakana, KanaMode, KanjiMode, Katakana, Romaji, Zenkaku,
+    ZenkakuHankaku,
+    // General purpose function keys, F1 to F24 are Key::F
+    Soft1, Soft2, Soft3, Soft4,
+    // Multimedia keys
+    ChannelDown, ChannelUp, Close, MailForward, MailReply, MailSend, MediaClose,
+    MediaFastForward, MediaPause, MediaPlay, MediaPlayPause, MediaRecord, MediaRewind,
+    MediaStop, MediaTrackNext, MediaTrackPrevious, New, Open, Print, Save, SpellCheck,
+    // Multimedia numpad keys
+    Key11, Key12,
+    // Audio keys
+    AudioBalanceLeft, AudioBalanceRight, AudioBassBoostDown, AudioBassBoostToggle,
+    AudioBassBoostUp, AudioFaderFront, AudioFaderRear, AudioSurroundModeNext, AudioTrebleDown,
+    AudioTrebleUp, AudioVolumeDown, AudioVolumeUp, AudioVolumeMute, MicrophoneToggle,
+    MicrophoneVolumeDown, MicrophoneVolumeUp, MicrophoneVolumeMute,
+    // Speech keys
+    SpeechCorrectionList, SpeechInputToggle,
+    // Application keys
+    LaunchApplication1, LaunchApplication2, LaunchCalendar, LaunchContacts, LaunchMail,
+    LaunchMediaPlayer, LaunchMusicPlayer, LaunchPhone, LaunchScreenSaver, LaunchSpreadsheet,
+    LaunchWebBrowser, LaunchWebCam, LaunchWordProcessor,
+    // Browser keys
+    BrowserBack, BrowserFavorites, BrowserForward, BrowserHome, BrowserRefresh, BrowserSearch,
+    BrowserStop,
+    // Mobile phone keys
+    AppSwitch, Call, Camera, CameraFocus, EndCall, GoBack, GoHome, HeadsetHook,
+    LastNumberRedial, Notification, MannerMode, VoiceDial,
+    // TV keys
+    TV, TV3DMode, TVAntennaCable, TVAudioDescription, TVAudioDescriptionMixDown,
+    TVAudioDescriptionMixUp, TVContentsMenu, TVDataService, TVInput, TVInputComponent1,
+    TVInputComponent2, TVInputComposite1, TVInputComposite2, TVInputHDMI1, TVInputHDMI2,
+    TVInputHDMI3, TVInputHDMI4, TVInputVGA1, TVMediaContext, TVNetwork, TVNumberEntry, TVPower,
+    TVRadioService, TVSatellite, TVSatelliteBS, TVSatelliteCS, TVSatelliteToggle,
+    TVTerrestrialAnalog, TVTerrestrialDigital, TVTimer,
+    // Media controller keys
+    AVRInput, AVRPower, ColorF0Red, ColorF1Green, ColorF2Yellow, ColorF3Blue, ColorF4Grey,
+    ColorF5Brown, ClosedCaptionToggle, Dimmer, DisplaySwap, DVR, Exit, FavoriteClear0,
+    FavoriteClear1, FavoriteClear2, FavoriteClear3, FavoriteRecall0, FavoriteRecall1,
+    FavoriteRecall2, FavoriteRecall3, FavoriteStore0, FavoriteStore1, FavoriteStore2,
+    FavoriteStore3, Guide, GuideNextDay, GuidePreviousDay, Info, InstantReplay, Link,
+    ListProgram, LiveContent, Lock, MediaApps, MediaAudioTrack, MediaLast, MediaSkipBackward,
+    MediaSkipForward, MediaStepBackward, MediaStepForward, MediaTopMenu, NavigateIn,
+    NavigateNext, NavigateOut, NavigatePrevious, NextFavoriteChannel, NextUserProfile,
+    OnDemand, Pairing, PinPDown, PinPMove, PinPToggle, PinPUp, PlaySpeedDown, PlaySpeedReset,
+    PlaySpeedUp, RandomToggle, RcLowBattery, RecordSpeedNext, RfBypass, ScanChannelsToggle,
+    ScreenModeNext, Settings, SplitScreenToggle, STBInput, STBPower, Subtitle, Teletext,
+    VideoModeNext, Wink, ZoomToggle,
+    // Special keys
+    Unidentified,
+} {
+    Any,
+    F(u8),
+    Character(char),
+});
+
+impl Key {
+    pub fn is_character(&self) -> bool {
+        matches!(self, Key::Character(_))
     }
 }
 
+// Keys are written as their KeyboardEvent.key value, except for the names poly-js
+// already matched before the full list was added
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Key::Any => write!(f, "any"),
             Key::Escape => write!(f, "escape"),
             Key::Enter => write!(f, "enter"),
-            Key::Key(key) => write!(f, "{}", key),
+            Key::Space => write!(f, " "),
+            Key::F(n) => write!(f, "F{}", n),
+            Key::Character(c) => write!(f, "{}", c),
+            key => write!(f, "{}", key.name().unwrap_or_default()),
         }
     }
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub struct ParseKeyError;
+pub struct ParseKeyError {
+    pub name: String,
+}
 
 impl fmt::Display for ParseKeyError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "unsupported key")
+        write!(f, "unknown key '{}'", self.name)
     }
 }
 
+impl std::error::Error for ParseKeyError {}
+
 impl FromStr for Key {
     type Err = ParseKeyError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "any" => Key::Any,
-            "escape" => Key::Escape,
-            "enter" => Key::Enter,
-            _ => Key::Key(s.to_string()),
-        })
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(' '), None) => Ok(Key::Space),
+            (Some(c), None) => Ok(Key::Character(c)),
+            _ if s.eq_ignore_ascii_case("any") => Ok(Key::Any),
+            _ if s.eq_ignore_ascii_case("esc") => Ok(Key::Escape),
+            _ => parse_function_key(s)
+                .map(Key::F)
+                .or_else(|| Key::from_name(s))
+                .ok_or_else(|| ParseKeyError {
+                    name: s.to_string(),
+                }),
+        }
+    }
+}
+
+named_keys!(Code {
+    // Writing system keys
+    Backquote, Backslash, BracketLeft, BracketRight, Comma, Equal, IntlBackslash, IntlRo,
+    IntlYen, Minus, Period, Quote, Semicolon, Slash,
+    // Functional keys
+    AltLeft, AltRight, Backspace, CapsLock, ContextMenu, ControlLeft, ControlRight, Enter,
+    MetaLeft, MetaRight, ShiftLeft, ShiftRight, Space, Tab,
+    // Control pad keys
+    Delete, End, Help, Home, Insert, PageDown, PageUp,
+    // Arrow pad keys
+    ArrowDown, ArrowLeft, ArrowRight, ArrowUp,
+    // Numpad keys
+    NumLock, NumpadAdd, NumpadDecimal, NumpadDivide, NumpadEnter, NumpadEqual,
+    NumpadMultiply, NumpadSubtract,
+    // Function keys
+    Escape, Fn, PrintScreen, ScrollLock, Pause,
+} {
+    Key(char),
+    Digit(u8),
+    Numpad(u8),
+    F(u8),
+});
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Code::Key(c) => write!(f, "Key{}", c.to_ascii_uppercase()),
+            Code::Digit(n) => write!(f, "Digit{}", n),
+            Code::Numpad(n) => write!(f, "Numpad{}", n),
+            Code::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{}", code.name().unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseCodeError {
+    pub name: String,
+}
+
+impl fmt::Display for ParseCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown key code '{}'", self.name)
+    }
+}
+
+impl std::error::Error for ParseCodeError {}
+
+impl FromStr for Code {
+    type Err = ParseCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let letter = strip_prefix_ignore_case(s, "Key").and_then(|rest| {
+            let mut chars = rest.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphabetic() => {
+                    Some(Code::Key(c.to_ascii_uppercase()))
+                }
+                _ => None,
+            }
+        });
+
+        let digit = |prefix| {
+            strip_prefix_ignore_case(s, prefix)
+                .filter(|rest| rest.len() == 1)
+                .and_then(|rest| rest.parse::<u8>().ok())
+        };
+
+        letter
+            .or_else(|| digit("Digit").map(Code::Digit))
+            .or_else(|| digit("Numpad").map(Code::Numpad))
+            .or_else(|| parse_function_key(s).map(Code::F))
+            .or_else(|| Code::from_name(s))
+            .ok_or_else(|| ParseCodeError {
+                name: s.to_string(),
+            })
+    }
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub meta: bool,
+}
+
+fn parse_function_key(s: &str) -> Option<u8> {
+    strip_prefix_ignore_case(s, "F")
+        .filter(|rest| !rest.starts_with('0') && rest.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|rest| rest.parse::<u8>().ok())
+        .filter(|n| (1..=24).contains(n))
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    match s.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&s[prefix.len()..]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_keep_their_wire_names() {
+        assert_eq!(Key::Escape.to_string(), "escape");
+        assert_eq!(Key::Enter.to_string(), "enter");
+        assert_eq!(Key::ArrowUp.to_string(), "ArrowUp");
+        assert_eq!(Key::TVInputHDMI1.to_string(), "TVInputHDMI1");
+        assert_eq!(Key::F(12).to_string(), "F12");
+        assert_eq!(Key::Space.to_string(), " ");
+    }
+
+    #[test]
+    fn keys_parse_case_insensitively() {
+        assert_eq!(Key::from_str("escape"), Ok(Key::Escape));
+        assert_eq!(Key::from_str("Escape"), Ok(Key::Escape));
+        assert_eq!(Key::from_str("esc"), Ok(Key::Escape));
+        assert_eq!(Key::from_str("ANY"), Ok(Key::Any));
+        assert_eq!(Key::from_str("mediarecord"), Ok(Key::MediaRecord));
+        assert_eq!(Key::from_str("f24"), Ok(Key::F(24)));
+        assert_eq!(Key::from_str("A"), Ok(Key::Character('A')));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        for name in ["", "F0", "F25", "Escapee", "any key"] {
+            assert_eq!(
+                Key::from_str(name),
+                Err(ParseKeyError {
+                    name: name.to_string(),
+                })
+            );
+        }
     }
 }
@@ -0,0 +1,20 @@
+use crate::browser::mouse::Position;
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointerInfo {
+    pub pointer_id: i32,
+    pub pointer_type: PointerType,
+    pub is_primary: bool,
+    pub pressure: f64,
+    pub position: Position,
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PointerType {
+    #[default]
+    Mouse,
+    Pen,
+    Touch,
+}
@@ -1,28 +1,363 @@
 use std::fmt;
 
-#[derive(Clone, serde::Serialize, serde::Deserialize)]
-pub struct Selector(String);
+// Selector lists ("a, b") are wrapped in :is() before they are extended or combined,
+// so "a, b" followed by " > c" becomes ":is(a, b) > c" instead of "a, b > c"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selector {
+    selector: String,
+    is_list: bool,
+}
 
 impl Selector {
+    pub fn raw(selector: &str) -> Selector {
+        Selector {
+            selector: selector.to_string(),
+            // A comma might also be inside a string or :not(), where wrapping is harmless
+            is_list: selector.contains(','),
+        }
+    }
+
+    pub fn any() -> Selector {
+        Selector::single("*".to_string())
+    }
+
+    pub fn tag(name: &str) -> Selector {
+        Selector::single(escape_identifier(name))
+    }
+
     pub fn id(id: &str) -> Selector {
-        Selector(format!("#{}", id))
+        Selector::empty().with_id(id)
+    }
+
+    pub fn class(class: &str) -> Selector {
+        Selector::empty().with_class(class)
+    }
+
+    pub fn attribute(name: &str) -> Selector {
+        Selector::empty().with_attribute(name)
+    }
+
+    pub fn attribute_value(name: &str, operator: AttributeOperator, value: &str) -> Selector {
+        Selector::empty().with_attribute_value(name, operator, value)
     }
 
     pub fn radio_group(name: &str) -> Selector {
-        Selector(format!("input[type=radio][name={}]", name))
+        Selector::tag("input")
+            .with_attribute_value("type", AttributeOperator::Equals, "radio")
+            .with_attribute_value("name", AttributeOperator::Equals, name)
     }
 
     pub fn data(name: &str) -> Selector {
-        Selector(format!("[data-{}]", name))
+        Selector::attribute(&format!("data-{}", name))
     }
 
     pub fn data_value(key: &str, value: &str) -> Selector {
-        Selector(format!("[data-{}=\"{}\"]", key, value))
+        Selector::attribute_value(&format!("data-{}", key), AttributeOperator::Equals, value)
+    }
+
+    pub fn with_id(self, id: &str) -> Selector {
+        self.append(&format!("#{}", escape_identifier(id)))
+    }
+
+    pub fn with_class(self, class: &str) -> Selector {
+        self.append(&format!(".{}", escape_identifier(class)))
+    }
+
+    pub fn with_attribute(self, name: &str) -> Selector {
+        self.append(&format!("[{}]", escape_identifier(name)))
+    }
+
+    pub fn with_attribute_value(
+        self,
+        name: &str,
+        operator: AttributeOperator,
+        value: &str,
+    ) -> Selector {
+        self.append(&format!(
+            "[{}{}{}]",
+            escape_identifier(name),
+            operator,
+            quote_string(value)
+        ))
+    }
+
+    pub fn not(self, selector: Selector) -> Selector {
+        self.append(&format!(":not({})", selector.selector))
+    }
+
+    pub fn is(self, selector: Selector) -> Selector {
+        self.append(&format!(":is({})", selector.selector))
+    }
+
+    pub fn nth_child(self, index: u32) -> Selector {
+        self.pseudo_class(&format!("nth-child({})", index))
+    }
+
+    pub fn nth_child_formula(self, a: i32, b: i32) -> Selector {
+        let formula = match (a, b) {
+            (0, b) => b.to_string(),
+            (a, 0) => format!("{}n", a),
+            (a, b) => format!("{}n{:+}", a, b),
+        };
+
+        self.pseudo_class(&format!("nth-child({})", formula))
+    }
+
+    pub fn first_child(self) -> Selector {
+        self.pseudo_class("first-child")
+    }
+
+    pub fn last_child(self) -> Selector {
+        self.pseudo_class("last-child")
+    }
+
+    pub fn descendant(self, selector: Selector) -> Selector {
+        self.combine(" ", selector)
+    }
+
+    pub fn child(self, selector: Selector) -> Selector {
+        self.combine(" > ", selector)
+    }
+
+    pub fn adjacent_sibling(self, selector: Selector) -> Selector {
+        self.combine(" + ", selector)
+    }
+
+    pub fn general_sibling(self, selector: Selector) -> Selector {
+        self.combine(" ~ ", selector)
+    }
+
+    pub fn or(self, selector: Selector) -> Selector {
+        Selector {
+            selector: format!("{}, {}", self.selector, selector.selector),
+            is_list: true,
+        }
+    }
+
+    fn pseudo_class(self, pseudo_class: &str) -> Selector {
+        self.append(&format!(":{}", pseudo_class))
+    }
+
+    fn append(self, s: &str) -> Selector {
+        Selector::single(format!("{}{}", self.into_complex(), s))
+    }
+
+    fn combine(self, combinator: &str, selector: Selector) -> Selector {
+        Selector::single(format!(
+            "{}{}{}",
+            self.into_complex(),
+            combinator,
+            selector.into_complex()
+        ))
+    }
+
+    fn into_complex(self) -> String {
+        if self.is_list {
+            format!(":is({})", self.selector)
+        } else {
+            self.selector
+        }
+    }
+
+    fn single(selector: String) -> Selector {
+        Selector {
+            selector,
+            is_list: false,
+        }
+    }
+
+    fn empty() -> Selector {
+        Selector::single(String::new())
     }
 }
 
 impl fmt::Display for Selector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.selector)
+    }
+}
+
+impl serde::Serialize for Selector {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.selector)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Selector {
+    fn deserialize<D>(deserializer: D) -> Result<Selector, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Selector::raw(&s))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeOperator {
+    Equals,
+    Includes,
+    DashMatch,
+    Prefix,
+    Suffix,
+    Contains,
+}
+
+impl fmt::Display for AttributeOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeOperator::Equals => write!(f, "="),
+            AttributeOperator::Includes => write!(f, "~="),
+            AttributeOperator::DashMatch => write!(f, "|="),
+            AttributeOperator::Prefix => write!(f, "^="),
+            AttributeOperator::Suffix => write!(f, "$="),
+            AttributeOperator::Contains => write!(f, "*="),
+        }
+    }
+}
+
+// Follows the CSS.escape() algorithm from the CSSOM spec
+pub fn escape_identifier(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut escaped = String::new();
+
+    for (index, c) in chars.iter().copied().enumerate() {
+        let starts_with_dash = chars.first() == Some(&'-');
+
+        match c {
+            '\0' => escaped.push('\u{FFFD}'),
+
+            '\u{1}'..='\u{1f}' | '\u{7f}' => escaped.push_str(&format!("\\{:x} ", c as u32)),
+
+            '0'..='9' if index == 0 || (index == 1 && starts_with_dash) => {
+                escaped.push_str(&format!("\\{:x} ", c as u32))
+            }
+
+            '-' if index == 0 && chars.len() == 1 => escaped.push_str("\\-"),
+
+            c if c >= '\u{80}' || c == '-' || c == '_' || c.is_ascii_alphanumeric() => {
+                escaped.push(c)
+            }
+
+            c => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+        }
+    }
+
+    escaped
+}
+
+fn quote_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+
+    for c in s.chars() {
+        match c {
+            '\0' => quoted.push('\u{FFFD}'),
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\u{1}'..='\u{1f}' | '\u{7f}' => quoted.push_str(&format!("\\{:x} ", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Examples from the CSSOM spec and the CSS.escape() test suite
+    #[test]
+    fn escape_identifier_matches_css_escape() {
+        let cases = [
+            ("\0", "\u{FFFD}"),
+            ("a\0", "a\u{FFFD}"),
+            ("\0b", "\u{FFFD}b"),
+            ("\u{1}\u{2}\u{1E}\u{1F}", "\\1 \\2 \\1e \\1f "),
+            ("\u{7F}", "\\7f "),
+            ("0a", "\\30 a"),
+            ("1a", "\\31 a"),
+            ("9a", "\\39 a"),
+            ("a0b", "a0b"),
+            ("-0a", "-\\30 a"),
+            ("-9a", "-\\39 a"),
+            ("--a", "--a"),
+            ("-", "\\-"),
+            ("-a", "-a"),
+            ("0", "\\30 "),
+            ("\u{80}-_\u{A9}", "\u{80}-_\u{A9}"),
+            ("\u{A0}\u{A1}\u{A2}", "\u{A0}\u{A1}\u{A2}"),
+            ("a0123456789b", "a0123456789b"),
+            ("abcdefghijklmnopqrstuvwxyz", "abcdefghijklmnopqrstuvwxyz"),
+            ("ABCDEFGHIJKLMNOPQRSTUVWXYZ", "ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+            ("\u{20}\u{21}\u{78}\u{79}", "\\ \\!xy"),
+            ("\u{1D306}", "\u{1D306}"),
+            (".foo#bar", "\\.foo\\#bar"),
+            ("()[]{}", "\\(\\)\\[\\]\\{\\}"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(escape_identifier(input), expected, "escaping {:?}", input);
+        }
+    }
+
+    #[test]
+    fn builds_compound_selectors() {
+        assert_eq!(
+            Selector::tag("input")
+                .with_class("a b")
+                .with_attribute_value("name", AttributeOperator::Equals, "x\"y")
+                .to_string(),
+            "input.a\\ b[name=\"x\\\"y\"]"
+        );
+
+        assert_eq!(Selector::id("1st").to_string(), "#\\31 st");
+    }
+
+    #[test]
+    fn wraps_lists_when_combined() {
+        let list = Selector::tag("a").or(Selector::tag("b"));
+
+        assert_eq!(list.to_string(), "a, b");
+        assert_eq!(
+            list.clone().child(Selector::tag("c")).to_string(),
+            ":is(a, b) > c"
+        );
+        assert_eq!(
+            Selector::tag("c").descendant(list.clone()).to_string(),
+            "c :is(a, b)"
+        );
+        assert_eq!(list.clone().with_class("x").to_string(), ":is(a, b).x");
+        assert_eq!(
+            list.clone().first_child().to_string(),
+            ":is(a, b):first-child"
+        );
+        assert_eq!(
+            Selector::tag("c").not(list.clone()).to_string(),
+            "c:not(a, b)"
+        );
+        assert_eq!(list.clone().or(Selector::tag("c")).to_string(), "a, b, c");
+        assert_eq!(
+            Selector::raw("a,b").child(Selector::tag("c")).to_string(),
+            ":is(a,b) > c"
+        );
+    }
+
+    #[test]
+    fn serializes_as_a_string() {
+        let selector = Selector::tag("a").or(Selector::tag("b"));
+        let json = serde_json::to_value(&selector).unwrap();
+
+        assert_eq!(json, serde_json::json!("a, b"));
+        assert_eq!(serde_json::from_value::<Selector>(json).unwrap(), selector);
     }
 }
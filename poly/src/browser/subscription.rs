@@ -1,9 +1,18 @@
 pub mod event_listener;
+pub mod intersection_observer;
 pub mod interval;
+pub mod shortcut;
+pub mod web_socket;
 
 use crate::browser::effect::Effect;
 use crate::browser::subscription::event_listener::EventListener;
+use crate::browser::subscription::event_listener::ListenerOptions;
+use crate::browser::subscription::intersection_observer::IntersectionObserver;
 use crate::browser::subscription::interval::Interval;
+use crate::browser::subscription::web_socket::WebSocket;
+use crate::browser::DebounceConfig;
+use crate::browser::RateLimit;
+use crate::browser::ThrottleConfig;
 
 #[derive(Clone, serde::Serialize)]
 #[serde(tag = "type", content = "config")]
@@ -12,6 +21,8 @@ pub enum Subscription<Msg> {
     None,
     EventListener(EventListener<Msg>),
     Interval(Interval<Msg>),
+    WebSocket(Box<WebSocket<Msg>>),
+    IntersectionObserver(IntersectionObserver<Msg>),
     Batch(Vec<Subscription<Msg>>),
 }
 
@@ -25,6 +36,107 @@ impl<Msg> Subscription<Msg> {
             _ => vec![self],
         }
     }
+
+    pub fn map<ToMsg, B>(self, to_msg: ToMsg) -> Subscription<B>
+    where
+        ToMsg: Fn(Msg) -> B,
+    {
+        self.map_ref(&to_msg)
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> Subscription<B> {
+        match self {
+            Subscription::None => Subscription::None,
+            Subscription::EventListener(listener) => {
+                Subscription::EventListener(listener.map_ref(to_msg))
+            }
+            Subscription::Interval(interval) => Subscription::Interval(interval.map_ref(to_msg)),
+            Subscription::WebSocket(web_socket) => {
+                Subscription::WebSocket(Box::new(web_socket.map_ref(to_msg)))
+            }
+            Subscription::IntersectionObserver(observer) => {
+                Subscription::IntersectionObserver(observer.map_ref(to_msg))
+            }
+            Subscription::Batch(subscriptions) => Subscription::Batch(
+                subscriptions
+                    .into_iter()
+                    .map(|subscription| subscription.map_ref(to_msg))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn debounce(self, config: DebounceConfig) -> Subscription<Msg> {
+        self.rate_limit(RateLimit::Debounce(config))
+    }
+
+    pub fn throttle(self, config: ThrottleConfig) -> Subscription<Msg> {
+        self.rate_limit(RateLimit::Throttle(config))
+    }
+
+    /// Applies to event listeners and intervals, including those inside a batch.
+    /// Any other subscription (`None`, web sockets, observers) is returned unchanged.
+    pub fn rate_limit(self, rate_limit: RateLimit) -> Subscription<Msg> {
+        match self {
+            Subscription::EventListener(mut listener) => {
+                listener.rate_limit = Some(rate_limit);
+                Subscription::EventListener(listener)
+            }
+
+            Subscription::Interval(mut interval) => {
+                interval.set_rate_limit(rate_limit);
+                Subscription::Interval(interval)
+            }
+
+            Subscription::Batch(subscriptions) => Subscription::Batch(
+                subscriptions
+                    .into_iter()
+                    .map(|subscription| subscription.rate_limit(rate_limit.clone()))
+                    .collect(),
+            ),
+
+            _ => self,
+        }
+    }
+
+    /// Keyboard listeners with exact modifiers only match when no other modifier is pressed,
+    /// i.e. `on_keyup(Key::Enter, msg).exact_modifiers(true)` ignores Shift+Enter.
+    pub fn exact_modifiers(self, exact: bool) -> Subscription<Msg> {
+        match self {
+            Subscription::EventListener(mut listener) => {
+                listener.set_exact_modifiers(exact);
+                Subscription::EventListener(listener)
+            }
+
+            Subscription::Batch(subscriptions) => Subscription::Batch(
+                subscriptions
+                    .into_iter()
+                    .map(|subscription| subscription.exact_modifiers(exact))
+                    .collect(),
+            ),
+
+            _ => self,
+        }
+    }
+
+    /// `passive` is ignored for listeners that prevent the default action.
+    pub fn listener_options(self, options: ListenerOptions) -> Subscription<Msg> {
+        match self {
+            Subscription::EventListener(mut listener) => {
+                listener.set_options(options);
+                Subscription::EventListener(listener)
+            }
+
+            Subscription::Batch(subscriptions) => Subscription::Batch(
+                subscriptions
+                    .into_iter()
+                    .map(|subscription| subscription.listener_options(options.clone()))
+                    .collect(),
+            ),
+
+            _ => self,
+        }
+    }
 }
 
 pub fn none<Msg>() -> Subscription<Msg> {
@@ -44,6 +156,23 @@ pub enum SubscriptionMsg<Msg> {
 }
 
 impl<Msg> SubscriptionMsg<Msg> {
+    pub fn map<ToMsg, B>(self, to_msg: ToMsg) -> SubscriptionMsg<B>
+    where
+        ToMsg: Fn(Msg) -> B,
+    {
+        self.map_ref(&to_msg)
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> SubscriptionMsg<B> {
+        match self {
+            SubscriptionMsg::Pure(msg) => SubscriptionMsg::Pure(to_msg(msg)),
+            SubscriptionMsg::Effectful { msg, effect } => SubscriptionMsg::Effectful {
+                msg: to_msg(msg),
+                effect: effect.map_ref(to_msg),
+            },
+        }
+    }
+
     pub fn pure(msg: Msg) -> SubscriptionMsg<Msg> {
         SubscriptionMsg::Pure(msg)
     }
@@ -1,12 +1,30 @@
 use crate::browser::dom_id::DomId;
 use crate::browser::effect::dom;
+use crate::browser::effect::navigation;
 use crate::browser::effect::Effect;
+use crate::browser::event::EventData;
+use crate::browser::event::EventField;
+use crate::browser::file::FileInfo;
+use crate::browser::keyboard::Code;
 use crate::browser::keyboard::Key;
 use crate::browser::mouse::Button;
+use crate::browser::mouse::Position;
+use crate::browser::mouse::WheelDelta;
+use crate::browser::pointer::PointerInfo;
 use crate::browser::selector::Selector;
 use crate::browser::subscription::Subscription;
 use crate::browser::subscription::SubscriptionMsg;
+use crate::browser::touch::LongPress;
+use crate::browser::touch::LongPressConfig;
+use crate::browser::touch::Pinch;
+use crate::browser::touch::Swipe;
+use crate::browser::touch::SwipeConfig;
+use crate::browser::touch::TouchInfo;
 use crate::browser::value::Capture;
+use crate::browser::DebounceConfig;
+use crate::browser::RateLimit;
+use crate::browser::ScrollPosition;
+use crate::browser::ThrottleConfig;
 use std::fmt;
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -14,6 +32,39 @@ use std::fmt;
 pub enum ListenTarget {
     Window,
     Document,
+    #[serde(rename_all = "camelCase")]
+    Element {
+        selector: Selector,
+    },
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerOptions {
+    pub passive: bool,
+    pub capture: bool,
+    pub once: bool,
+}
+
+impl ListenerOptions {
+    pub fn new() -> ListenerOptions {
+        ListenerOptions::default()
+    }
+
+    pub fn passive(mut self, passive: bool) -> ListenerOptions {
+        self.passive = passive;
+        self
+    }
+
+    pub fn capture(mut self, capture: bool) -> ListenerOptions {
+        self.capture = capture;
+        self
+    }
+
+    pub fn once(mut self, once: bool) -> ListenerOptions {
+        self.once = once;
+        self
+    }
 }
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -34,7 +85,89 @@ pub enum EventMatcher {
         key: Key,
         requires_ctrl: bool,
         requires_meta: bool,
+        requires_alt: bool,
+        requires_shift: bool,
+        requires_primary: bool,
+        exact_modifiers: bool,
+        // Shift is part of printable keys ("?" or "A"), so it is not compared for them
+        ignore_shift: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    KeyboardCode {
+        code: Code,
+        requires_ctrl: bool,
+        requires_meta: bool,
+        requires_alt: bool,
+        requires_shift: bool,
+        requires_primary: bool,
+        exact_modifiers: bool,
+    },
+    InternalLink,
+    NotEditable,
+    #[serde(rename_all = "camelCase")]
+    KeySequence {
+        steps: Vec<EventMatcher>,
+        timeout: u32,
     },
+    SwipeGesture(SwipeConfig),
+    LongPressGesture(LongPressConfig),
+}
+
+impl EventMatcher {
+    // Returns whether the matcher changed
+    fn set_exact_modifiers(&mut self, exact: bool) -> bool {
+        match self {
+            EventMatcher::KeyboardKey {
+                exact_modifiers, ..
+            }
+            | EventMatcher::KeyboardCode {
+                exact_modifiers, ..
+            } => std::mem::replace(exact_modifiers, exact) != exact,
+
+            EventMatcher::KeySequence { steps, .. } => {
+                let mut changed = false;
+
+                for step in steps {
+                    changed |= step.set_exact_modifiers(exact);
+                }
+
+                changed
+            }
+
+            _ => false,
+        }
+    }
+
+    pub fn keyboard_key(key: Key, modifier: &ModifierKey, exact_modifiers: bool) -> EventMatcher {
+        let ignore_shift = key.is_character();
+
+        EventMatcher::KeyboardKey {
+            key,
+            requires_ctrl: modifier.requires_ctrl(),
+            requires_meta: modifier.requires_meta(),
+            requires_alt: modifier.requires_alt(),
+            requires_shift: modifier.requires_shift() && !ignore_shift,
+            requires_primary: modifier.requires_primary(),
+            exact_modifiers,
+            ignore_shift,
+        }
+    }
+
+    pub fn keyboard_code(
+        code: Code,
+        modifier: &ModifierKey,
+        exact_modifiers: bool,
+    ) -> EventMatcher {
+        EventMatcher::KeyboardCode {
+            code,
+            requires_ctrl: modifier.requires_ctrl(),
+            requires_meta: modifier.requires_meta(),
+            requires_alt: modifier.requires_alt(),
+            requires_shift: modifier.requires_shift(),
+            requires_primary: modifier.requires_primary(),
+            exact_modifiers,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -42,6 +175,10 @@ pub enum ModifierKey {
     None,
     Ctrl,
     Meta,
+    Alt,
+    Shift,
+    // Meta on macOS and Ctrl elsewhere, resolved by poly-js
+    Primary,
     Multiple(Vec<ModifierKey>),
 }
 
@@ -61,6 +198,30 @@ impl ModifierKey {
             _ => false,
         }
     }
+
+    pub fn requires_alt(&self) -> bool {
+        match self {
+            ModifierKey::Alt => true,
+            ModifierKey::Multiple(keys) => keys.iter().any(Self::requires_alt),
+            _ => false,
+        }
+    }
+
+    pub fn requires_shift(&self) -> bool {
+        match self {
+            ModifierKey::Shift => true,
+            ModifierKey::Multiple(keys) => keys.iter().any(Self::requires_shift),
+            _ => false,
+        }
+    }
+
+    pub fn requires_primary(&self) -> bool {
+        match self {
+            ModifierKey::Primary => true,
+            ModifierKey::Multiple(keys) => keys.iter().any(Self::requires_primary),
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for ModifierKey {
@@ -69,6 +230,9 @@ impl fmt::Display for ModifierKey {
             ModifierKey::None => write!(f, "no-modifier"),
             ModifierKey::Ctrl => write!(f, "ctrl"),
             ModifierKey::Meta => write!(f, "meta"),
+            ModifierKey::Alt => write!(f, "alt"),
+            ModifierKey::Shift => write!(f, "shift"),
+            ModifierKey::Primary => write!(f, "primary"),
             ModifierKey::Multiple(keys) => {
                 let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
                 write!(f, "{}", keys.join("-"))
@@ -86,6 +250,45 @@ pub struct EventListener<Msg> {
     pub matchers: Vec<EventMatcher>,
     pub msg: SubscriptionMsg<Msg>,
     pub propagation: EventPropagation,
+    pub rate_limit: Option<RateLimit>,
+    pub options: ListenerOptions,
+}
+
+impl<Msg> EventListener<Msg> {
+    // Browsers ignore preventDefault in passive listeners, so passive is dropped when it's needed
+    pub(crate) fn set_options(&mut self, options: ListenerOptions) {
+        self.options = ListenerOptions {
+            passive: options.passive && !self.propagation.prevent_default,
+            ..options
+        };
+    }
+
+    pub(crate) fn set_exact_modifiers(&mut self, exact: bool) {
+        let mut changed = false;
+
+        for matcher in &mut self.matchers {
+            changed |= matcher.set_exact_modifiers(exact);
+        }
+
+        // Keep the id distinct from the same listener with the other modifier semantics
+        if changed {
+            let suffix = if exact { "exact" } else { "any-modifiers" };
+            self.id = format!("{}-{}", self.id, suffix);
+        }
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> EventListener<B> {
+        EventListener {
+            id: self.id,
+            listen_target: self.listen_target,
+            event_type: self.event_type,
+            matchers: self.matchers,
+            msg: self.msg.map_ref(to_msg),
+            propagation: self.propagation,
+            rate_limit: self.rate_limit,
+            options: self.options,
+        }
+    }
 }
 
 pub fn on_click<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
@@ -104,6 +307,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -123,6 +328,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -145,6 +352,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -167,6 +376,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -191,6 +402,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -211,9 +424,23 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
+pub fn on_input_debounced<Id, Msg, ToMsg>(
+    id: Id,
+    config: DebounceConfig,
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<String>) -> Msg,
+{
+    on_input(id, to_msg).debounce(config)
+}
+
 pub fn on_change<Id, Msg, ToMsg, T>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
 where
     Id: DomId,
@@ -232,6 +459,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -252,6 +481,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -274,6 +505,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -294,6 +527,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -315,6 +550,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -334,6 +571,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -354,6 +593,8 @@ where
             stop_propagation: true,
             prevent_default: true,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -362,16 +603,14 @@ pub fn on_keyup<Msg>(key: Key, msg: Msg) -> Subscription<Msg> {
         id: format!("keyboard-keyup-{}", key),
         listen_target: ListenTarget::Document,
         event_type: EventType::Keyup,
-        matchers: vec![EventMatcher::KeyboardKey {
-            key,
-            requires_ctrl: false,
-            requires_meta: false,
-        }],
+        matchers: vec![EventMatcher::keyboard_key(key, &ModifierKey::None, false)],
         msg: SubscriptionMsg::pure(msg),
         propagation: EventPropagation {
             stop_propagation: false,
             prevent_default: false,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -380,16 +619,14 @@ pub fn on_keydown<Msg>(key: Key, modifier: ModifierKey, msg: Msg) -> Subscriptio
         id: format!("keyboard-keydown-{}-{}", key, modifier),
         listen_target: ListenTarget::Document,
         event_type: EventType::Keydown,
-        matchers: vec![EventMatcher::KeyboardKey {
-            key,
-            requires_ctrl: modifier.requires_ctrl(),
-            requires_meta: modifier.requires_meta(),
-        }],
+        matchers: vec![EventMatcher::keyboard_key(key, &modifier, false)],
         msg: SubscriptionMsg::pure(msg),
         propagation: EventPropagation {
             stop_propagation: false,
             prevent_default: false,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -403,13 +640,27 @@ pub fn on_keydown_with_options<Msg>(
         id: format!("keyboard-keydown-{}-{}", key, modifier),
         listen_target: ListenTarget::Document,
         event_type: EventType::Keydown,
-        matchers: vec![EventMatcher::KeyboardKey {
-            key,
-            requires_ctrl: modifier.requires_ctrl(),
-            requires_meta: modifier.requires_meta(),
-        }],
+        matchers: vec![EventMatcher::keyboard_key(key, &modifier, false)],
         msg: SubscriptionMsg::pure(msg),
         propagation,
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_keydown_code<Msg>(code: Code, modifier: ModifierKey, msg: Msg) -> Subscription<Msg> {
+    Subscription::EventListener(EventListener {
+        id: format!("keyboard-keydown-code-{}-{}", code, modifier),
+        listen_target: ListenTarget::Document,
+        event_type: EventType::Keydown,
+        matchers: vec![EventMatcher::keyboard_code(code, &modifier, false)],
+        msg: SubscriptionMsg::pure(msg),
+        propagation: EventPropagation {
+            stop_propagation: false,
+            prevent_default: false,
+        },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
@@ -428,9 +679,636 @@ where
             stop_propagation: false,
             prevent_default: false,
         },
+        rate_limit: None,
+        options: ListenerOptions::default(),
     })
 }
 
+pub fn on_popstate<Msg, ToMsg, T>(to_msg: ToMsg) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<T>) -> Msg,
+    T: Default,
+{
+    Subscription::EventListener(EventListener {
+        id: "window-popstate".to_string(),
+        listen_target: ListenTarget::Window,
+        event_type: EventType::Popstate,
+        matchers: vec![],
+        msg: SubscriptionMsg::effectful(to_msg, navigation::get_current_url()),
+        propagation: EventPropagation {
+            stop_propagation: false,
+            prevent_default: false,
+        },
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_internal_link_click<Msg, ToMsg, T>(to_msg: ToMsg) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<T>) -> Msg,
+    T: Default,
+{
+    Subscription::EventListener(EventListener {
+        id: "internal-link-click".to_string(),
+        listen_target: ListenTarget::Document,
+        event_type: EventType::Click,
+        matchers: vec![EventMatcher::InternalLink],
+        msg: SubscriptionMsg::effectful(to_msg, navigation::follow_link()),
+        propagation: EventPropagation {
+            stop_propagation: false,
+            prevent_default: true,
+        },
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_window_resize_throttled<Msg, ToMsg, T>(
+    config: ThrottleConfig,
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<T>) -> Msg,
+    T: Default,
+{
+    on_window_resize(to_msg).throttle(config)
+}
+
+pub fn on_focus<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        EventType::Focus,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_blur<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        EventType::Blur,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_focus_in<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    element_listener(
+        id,
+        EventType::Focusin,
+        SubscriptionMsg::pure(msg),
+        default_propagation(),
+    )
+}
+
+pub fn on_focus_out<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    element_listener(
+        id,
+        EventType::Focusout,
+        SubscriptionMsg::pure(msg),
+        default_propagation(),
+    )
+}
+
+pub fn on_scroll<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<ScrollPosition>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_scroll_position());
+    attached_listener(
+        id,
+        EventType::Scroll,
+        msg,
+        ListenerOptions::new().passive(true),
+    )
+}
+
+pub fn on_window_scroll<Msg, ToMsg>(to_msg: ToMsg) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<ScrollPosition>) -> Msg,
+{
+    Subscription::EventListener(EventListener {
+        id: "window-scroll".to_string(),
+        listen_target: ListenTarget::Window,
+        event_type: EventType::Scroll,
+        matchers: vec![],
+        msg: SubscriptionMsg::effectful(to_msg, dom::get_scroll_position()),
+        propagation: default_propagation(),
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_mouse_move<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<Position>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_mouse_position());
+    element_listener(id, EventType::Mousemove, msg, default_propagation())
+}
+
+pub fn on_mouse_up<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    Subscription::EventListener(EventListener {
+        id: format!("{}-{}", EventType::Mouseup.as_str(), id),
+        listen_target: ListenTarget::Document,
+        matchers: vec![
+            EventMatcher::ExactSelector {
+                selector: id.selector(),
+            },
+            EventMatcher::MouseButton {
+                button: Button::Main,
+            },
+        ],
+        event_type: EventType::Mouseup,
+        msg: SubscriptionMsg::pure(msg),
+        propagation: EventPropagation {
+            stop_propagation: true,
+            prevent_default: true,
+        },
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_mouse_enter<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        EventType::Mouseenter,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_mouse_leave<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        EventType::Mouseleave,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_pointer_down<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<PointerInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_pointer_info());
+    element_listener(id, EventType::Pointerdown, msg, default_propagation())
+}
+
+pub fn on_pointer_move<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<PointerInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_pointer_info());
+    element_listener(id, EventType::Pointermove, msg, default_propagation())
+}
+
+pub fn on_pointer_up<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<PointerInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_pointer_info());
+    element_listener(id, EventType::Pointerup, msg, default_propagation())
+}
+
+pub fn on_pointer_cancel<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<PointerInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_pointer_info());
+    element_listener(id, EventType::Pointercancel, msg, default_propagation())
+}
+
+pub fn on_pointer_enter<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        EventType::Pointerenter,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_pointer_leave<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        EventType::Pointerleave,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_wheel<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<WheelDelta>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_wheel_delta());
+    element_listener(id, EventType::Wheel, msg, default_propagation())
+}
+
+pub fn on_context_menu<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<Position>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_mouse_position());
+    element_listener(id, EventType::Contextmenu, msg, stop_and_prevent())
+}
+
+pub fn on_double_click<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    element_listener(
+        id,
+        EventType::Dblclick,
+        SubscriptionMsg::pure(msg),
+        stop_and_prevent(),
+    )
+}
+
+pub fn on_drag_start<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    // Preventing the default would cancel the drag
+    let propagation = EventPropagation {
+        stop_propagation: true,
+        prevent_default: false,
+    };
+
+    element_listener(
+        id,
+        EventType::Dragstart,
+        SubscriptionMsg::pure(msg),
+        propagation,
+    )
+}
+
+pub fn on_drag_over<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    // The default has to be prevented for the element to accept drops
+    element_listener(
+        id,
+        EventType::Dragover,
+        SubscriptionMsg::pure(msg),
+        stop_and_prevent(),
+    )
+}
+
+pub fn on_drop<Id, Msg>(id: Id, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    element_listener(
+        id,
+        EventType::Drop,
+        SubscriptionMsg::pure(msg),
+        stop_and_prevent(),
+    )
+}
+
+pub fn on_drop_files<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<Vec<FileInfo>>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_dropped_files());
+    element_listener(id, EventType::Drop, msg, stop_and_prevent())
+}
+
+pub fn on_touch_start<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<TouchInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_touches());
+    element_listener(id, EventType::Touchstart, msg, default_propagation())
+}
+
+pub fn on_touch_move<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<TouchInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_touches());
+    element_listener(id, EventType::Touchmove, msg, default_propagation())
+}
+
+pub fn on_touch_end<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<TouchInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_touches());
+    element_listener(id, EventType::Touchend, msg, default_propagation())
+}
+
+pub fn on_touch_cancel<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<TouchInfo>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_touches());
+    element_listener(id, EventType::Touchcancel, msg, default_propagation())
+}
+
+pub fn on_swipe<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<Swipe>) -> Msg,
+{
+    on_swipe_with_config(id, SwipeConfig::default(), to_msg)
+}
+
+pub fn on_swipe_with_config<Id, Msg, ToMsg>(
+    id: Id,
+    config: SwipeConfig,
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<Swipe>) -> Msg,
+{
+    Subscription::EventListener(EventListener {
+        id: format!("{}-{}", EventType::Swipe.as_str(), id),
+        listen_target: ListenTarget::Document,
+        matchers: vec![
+            EventMatcher::ExactSelector {
+                selector: id.selector(),
+            },
+            EventMatcher::SwipeGesture(config),
+        ],
+        event_type: EventType::Swipe,
+        msg: SubscriptionMsg::effectful(to_msg, dom::get_gesture()),
+        propagation: default_propagation(),
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_long_press<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<LongPress>) -> Msg,
+{
+    on_long_press_with_config(id, LongPressConfig::default(), to_msg)
+}
+
+pub fn on_long_press_with_config<Id, Msg, ToMsg>(
+    id: Id,
+    config: LongPressConfig,
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<LongPress>) -> Msg,
+{
+    Subscription::EventListener(EventListener {
+        id: format!("{}-{}", EventType::LongPress.as_str(), id),
+        listen_target: ListenTarget::Document,
+        matchers: vec![
+            EventMatcher::ExactSelector {
+                selector: id.selector(),
+            },
+            EventMatcher::LongPressGesture(config),
+        ],
+        event_type: EventType::LongPress,
+        msg: SubscriptionMsg::effectful(to_msg, dom::get_gesture()),
+        // Suppress the native context menu that follows a long press
+        propagation: stop_and_prevent(),
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_pinch<Id, Msg, ToMsg>(id: Id, to_msg: ToMsg) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<Pinch>) -> Msg,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, dom::get_gesture());
+    element_listener(id, EventType::Pinch, msg, default_propagation())
+}
+
+pub fn on_event_data<Id, Msg, ToMsg>(
+    id: Id,
+    event_type: EventType,
+    fields: &[EventField],
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<EventData>) -> Msg,
+{
+    Subscription::EventListener(EventListener {
+        id: format!(
+            "event-data-{}-{}-{}",
+            event_type.as_str(),
+            field_names(fields),
+            id
+        ),
+        listen_target: ListenTarget::Document,
+        matchers: vec![EventMatcher::ExactSelector {
+            selector: id.selector(),
+        }],
+        event_type,
+        msg: SubscriptionMsg::effectful(to_msg, dom::get_event_data(fields)),
+        propagation: default_propagation(),
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_document_event_data<Msg, ToMsg>(
+    event_type: EventType,
+    fields: &[EventField],
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<EventData>) -> Msg,
+{
+    Subscription::EventListener(EventListener {
+        id: format!(
+            "document-event-data-{}-{}",
+            event_type.as_str(),
+            field_names(fields)
+        ),
+        listen_target: ListenTarget::Document,
+        event_type,
+        matchers: vec![],
+        msg: SubscriptionMsg::effectful(to_msg, dom::get_event_data(fields)),
+        propagation: default_propagation(),
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+pub fn on_keydown_data<Msg, ToMsg>(to_msg: ToMsg) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<EventData>) -> Msg,
+{
+    let fields = [
+        EventField::Key,
+        EventField::Code,
+        EventField::Modifiers,
+        EventField::TargetId,
+        EventField::SelectionStart,
+        EventField::TimeStamp,
+    ];
+
+    on_document_event_data(EventType::Keydown, &fields, to_msg)
+}
+
+pub fn on_element<Id, Msg>(id: Id, event_type: EventType, msg: Msg) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    attached_listener(
+        id,
+        event_type,
+        SubscriptionMsg::pure(msg),
+        ListenerOptions::default(),
+    )
+}
+
+pub fn on_element_with_effect<Id, Msg, ToMsg, T>(
+    id: Id,
+    event_type: EventType,
+    effect: Effect<Msg>,
+    to_msg: ToMsg,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+    ToMsg: Fn(Capture<T>) -> Msg,
+    T: Default,
+{
+    let msg = SubscriptionMsg::effectful(to_msg, effect);
+    attached_listener(id, event_type, msg, ListenerOptions::default())
+}
+
+pub fn on_element_selector<Msg>(
+    selector: Selector,
+    event_type: EventType,
+    msg: Msg,
+) -> Subscription<Msg> {
+    Subscription::EventListener(EventListener {
+        id: format!("element-{}-{}", event_type.as_str(), selector),
+        listen_target: ListenTarget::Element { selector },
+        event_type,
+        matchers: vec![],
+        msg: SubscriptionMsg::pure(msg),
+        propagation: default_propagation(),
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+fn attached_listener<Id, Msg>(
+    id: Id,
+    event_type: EventType,
+    msg: SubscriptionMsg<Msg>,
+    options: ListenerOptions,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    Subscription::EventListener(EventListener {
+        id: format!("{}-{}", event_type.as_str(), id),
+        listen_target: ListenTarget::Element {
+            selector: id.selector(),
+        },
+        event_type,
+        matchers: vec![],
+        msg,
+        propagation: default_propagation(),
+        rate_limit: None,
+        options,
+    })
+}
+
+fn element_listener<Id, Msg>(
+    id: Id,
+    event_type: EventType,
+    msg: SubscriptionMsg<Msg>,
+    propagation: EventPropagation,
+) -> Subscription<Msg>
+where
+    Id: DomId,
+{
+    Subscription::EventListener(EventListener {
+        id: format!("{}-{}", event_type.as_str(), id),
+        listen_target: ListenTarget::Document,
+        matchers: vec![EventMatcher::ExactSelector {
+            selector: id.selector(),
+        }],
+        event_type,
+        msg,
+        propagation,
+        rate_limit: None,
+        options: ListenerOptions::default(),
+    })
+}
+
+// Listeners for different fields must not share an id
+fn field_names(fields: &[EventField]) -> String {
+    let mut names: Vec<&str> = fields.iter().map(EventField::as_str).collect();
+    names.sort_unstable();
+    names.dedup();
+    names.join("-")
+}
+
+fn default_propagation() -> EventPropagation {
+    EventPropagation {
+        stop_propagation: false,
+        prevent_default: false,
+    }
+}
+
+fn stop_and_prevent() -> EventPropagation {
+    EventPropagation {
+        stop_propagation: true,
+        prevent_default: true,
+    }
+}
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EventPropagation {
@@ -438,7 +1316,7 @@ pub struct EventPropagation {
     pub prevent_default: bool,
 }
 
-#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum EventType {
     Click,
@@ -449,4 +1327,91 @@ pub enum EventType {
     Keyup,
     Keydown,
     Resize,
+    Popstate,
+    Focus,
+    Blur,
+    Focusin,
+    Focusout,
+    Scroll,
+    Mousemove,
+    Mouseup,
+    Mouseenter,
+    Mouseleave,
+    Pointerdown,
+    Pointermove,
+    Pointerup,
+    Pointercancel,
+    Pointerenter,
+    Pointerleave,
+    Wheel,
+    Contextmenu,
+    Dblclick,
+    Dragstart,
+    Dragover,
+    Drop,
+    Touchstart,
+    Touchmove,
+    Touchend,
+    Touchcancel,
+    Swipe,
+    LongPress,
+    Pinch,
+    Play,
+    Pause,
+    Ended,
+    Timeupdate,
+    Volumechange,
+    Loadedmetadata,
+    Seeked,
+}
+
+impl EventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventType::Click => "click",
+            EventType::Mousedown => "mousedown",
+            EventType::Input => "input",
+            EventType::Change => "change",
+            EventType::Submit => "submit",
+            EventType::Keyup => "keyup",
+            EventType::Keydown => "keydown",
+            EventType::Resize => "resize",
+            EventType::Popstate => "popstate",
+            EventType::Focus => "focus",
+            EventType::Blur => "blur",
+            EventType::Focusin => "focusin",
+            EventType::Focusout => "focusout",
+            EventType::Scroll => "scroll",
+            EventType::Mousemove => "mousemove",
+            EventType::Mouseup => "mouseup",
+            EventType::Mouseenter => "mouseenter",
+            EventType::Mouseleave => "mouseleave",
+            EventType::Pointerdown => "pointerdown",
+            EventType::Pointermove => "pointermove",
+            EventType::Pointerup => "pointerup",
+            EventType::Pointercancel => "pointercancel",
+            EventType::Pointerenter => "pointerenter",
+            EventType::Pointerleave => "pointerleave",
+            EventType::Wheel => "wheel",
+            EventType::Contextmenu => "contextmenu",
+            EventType::Dblclick => "dblclick",
+            EventType::Dragstart => "dragstart",
+            EventType::Dragover => "dragover",
+            EventType::Drop => "drop",
+            EventType::Touchstart => "touchstart",
+            EventType::Touchmove => "touchmove",
+            EventType::Touchend => "touchend",
+            EventType::Touchcancel => "touchcancel",
+            EventType::Swipe => "swipe",
+            EventType::LongPress => "longPress",
+            EventType::Pinch => "pinch",
+            EventType::Play => "play",
+            EventType::Pause => "pause",
+            EventType::Ended => "ended",
+            EventType::Timeupdate => "timeupdate",
+            EventType::Volumechange => "volumechange",
+            EventType::Loadedmetadata => "loadedmetadata",
+            EventType::Seeked => "seeked",
+        }
+    }
 }
@@ -0,0 +1,222 @@
+use crate::browser::dom_id::DomId;
+use crate::browser::effect::dom;
+use crate::browser::selector::Selector;
+use crate::browser::subscription::Subscription;
+use crate::browser::subscription::SubscriptionMsg;
+use crate::browser::value::Capture;
+use std::fmt;
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntersectionObserver<Msg> {
+    pub id: String,
+    pub selector: Selector,
+    pub root_margin: RootMargin,
+    pub thresholds: Vec<f64>,
+    pub on_enter: Option<SubscriptionMsg<Msg>>,
+    pub on_leave: Option<SubscriptionMsg<Msg>>,
+}
+
+impl<Msg> IntersectionObserver<Msg> {
+    pub fn element<Id>(id: Id) -> IntersectionObserver<Msg>
+    where
+        Id: DomId,
+    {
+        IntersectionObserver::selector(id.selector())
+    }
+
+    pub fn selector(selector: Selector) -> IntersectionObserver<Msg> {
+        IntersectionObserver {
+            id: String::new(),
+            selector,
+            root_margin: RootMargin::default(),
+            thresholds: vec![0.0],
+            on_enter: None,
+            on_leave: None,
+        }
+        .with_config_id()
+    }
+
+    // Observers with a different config must not share an id, so that changes are picked up
+    fn with_config_id(mut self) -> IntersectionObserver<Msg> {
+        let thresholds: Vec<String> = self.thresholds.iter().map(f64::to_string).collect();
+
+        self.id = format!(
+            "intersection-{}-{}-{}",
+            self.selector,
+            self.root_margin,
+            thresholds.join(",")
+        );
+
+        self
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> IntersectionObserver<B> {
+        IntersectionObserver {
+            id: self.id,
+            selector: self.selector,
+            root_margin: self.root_margin,
+            thresholds: self.thresholds,
+            on_enter: self.on_enter.map(|msg| msg.map_ref(to_msg)),
+            on_leave: self.on_leave.map(|msg| msg.map_ref(to_msg)),
+        }
+    }
+
+    pub fn root_margin(mut self, root_margin: RootMargin) -> IntersectionObserver<Msg> {
+        self.root_margin = root_margin;
+        self.with_config_id()
+    }
+
+    /// Thresholds are clamped to 0..=1 and NaN is dropped. Without any valid threshold
+    /// the current thresholds are kept.
+    pub fn thresholds(mut self, thresholds: &[f64]) -> IntersectionObserver<Msg> {
+        let thresholds: Vec<f64> = thresholds
+            .iter()
+            .filter(|threshold| !threshold.is_nan())
+            .map(|threshold| threshold.clamp(0.0, 1.0))
+            .collect();
+
+        if !thresholds.is_empty() {
+            self.thresholds = thresholds;
+        }
+
+        self.with_config_id()
+    }
+
+    pub fn on_enter<ToMsg>(mut self, to_msg: ToMsg) -> IntersectionObserver<Msg>
+    where
+        ToMsg: Fn(Capture<IntersectionEntry>) -> Msg,
+    {
+        self.on_enter = Some(SubscriptionMsg::effectful(
+            to_msg,
+            dom::get_intersection_entry(),
+        ));
+        self
+    }
+
+    pub fn on_leave<ToMsg>(mut self, to_msg: ToMsg) -> IntersectionObserver<Msg>
+    where
+        ToMsg: Fn(Capture<IntersectionEntry>) -> Msg,
+    {
+        self.on_leave = Some(SubscriptionMsg::effectful(
+            to_msg,
+            dom::get_intersection_entry(),
+        ));
+        self
+    }
+}
+
+pub fn intersection_observer<Msg>(config: IntersectionObserver<Msg>) -> Subscription<Msg> {
+    Subscription::IntersectionObserver(config)
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntersectionEntry {
+    /// The id attribute of the observed element, or an empty string when it has none.
+    pub target_id: String,
+    pub intersection_ratio: f64,
+    pub is_intersecting: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RootMargin {
+    pub top: Length,
+    pub right: Length,
+    pub bottom: Length,
+    pub left: Length,
+}
+
+impl RootMargin {
+    pub fn new(top: Length, right: Length, bottom: Length, left: Length) -> RootMargin {
+        RootMargin {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    pub fn all(length: Length) -> RootMargin {
+        RootMargin::new(length, length, length, length)
+    }
+
+    pub fn symmetric(vertical: Length, horizontal: Length) -> RootMargin {
+        RootMargin::new(vertical, horizontal, vertical, horizontal)
+    }
+}
+
+impl fmt::Display for RootMargin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.top, self.right, self.bottom, self.left
+        )
+    }
+}
+
+impl serde::Serialize for RootMargin {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+// IntersectionObserver only accepts pixels and percentages of the root
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Length {
+    Px(i32),
+    Percent(i32),
+}
+
+impl Default for Length {
+    fn default() -> Self {
+        Length::Px(0)
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Length::Px(px) => write!(f, "{}px", px),
+            Length::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observer() -> IntersectionObserver<()> {
+        IntersectionObserver::selector(Selector::raw("#list"))
+    }
+
+    #[test]
+    fn thresholds_drop_nan_and_keep_current_when_empty() {
+        assert_eq!(
+            observer().thresholds(&[f64::NAN, 2.0, 0.5]).thresholds,
+            vec![1.0, 0.5]
+        );
+        assert_eq!(observer().thresholds(&[f64::NAN]).thresholds, vec![0.0]);
+        assert_eq!(observer().thresholds(&[]).thresholds, vec![0.0]);
+    }
+
+    #[test]
+    fn id_includes_config() {
+        assert_eq!(observer().id, "intersection-#list-0px 0px 0px 0px-0");
+
+        assert_ne!(
+            observer().thresholds(&[0.5]).id,
+            observer().thresholds(&[1.0]).id
+        );
+
+        assert_ne!(
+            observer().id,
+            observer().root_margin(RootMargin::all(Length::Px(10))).id
+        );
+    }
+}
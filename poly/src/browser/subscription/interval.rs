@@ -2,14 +2,33 @@ use crate::browser::effect::Effect;
 use crate::browser::subscription::Subscription;
 use crate::browser::subscription::SubscriptionMsg;
 use crate::browser::value::Capture;
+use crate::browser::DebounceConfig;
+use crate::browser::RateLimit;
+use crate::browser::ThrottleConfig;
 use std::time::Duration;
 
 #[derive(Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Interval<Msg> {
     id: String,
-    duration: u64,
-    msg: SubscriptionMsg<Msg>,
+    pub(crate) duration: u64,
+    pub(crate) msg: SubscriptionMsg<Msg>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl<Msg> Interval<Msg> {
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> Interval<B> {
+        Interval {
+            id: self.id,
+            duration: self.duration,
+            msg: self.msg.map_ref(to_msg),
+            rate_limit: self.rate_limit,
+        }
+    }
+
+    pub fn set_rate_limit(&mut self, rate_limit: RateLimit) {
+        self.rate_limit = Some(rate_limit);
+    }
 }
 
 pub fn interval<Msg>(duration: Duration, msg: Msg) -> Subscription<Msg> {
@@ -17,6 +36,7 @@ pub fn interval<Msg>(duration: Duration, msg: Msg) -> Subscription<Msg> {
         id: format!("interval-{}", duration.as_millis()),
         duration: duration.as_millis() as u64,
         msg: SubscriptionMsg::pure(msg),
+        rate_limit: None,
     })
 }
 
@@ -33,5 +53,22 @@ where
         id: format!("interval-{}", duration.as_millis()),
         duration: duration.as_millis() as u64,
         msg: SubscriptionMsg::effectful(to_msg, effect),
+        rate_limit: None,
     })
 }
+
+pub fn interval_debounced<Msg>(
+    duration: Duration,
+    config: DebounceConfig,
+    msg: Msg,
+) -> Subscription<Msg> {
+    interval(duration, msg).debounce(config)
+}
+
+pub fn interval_throttled<Msg>(
+    duration: Duration,
+    config: ThrottleConfig,
+    msg: Msg,
+) -> Subscription<Msg> {
+    interval(duration, msg).throttle(config)
+}
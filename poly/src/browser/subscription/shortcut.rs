@@ -0,0 +1,547 @@
+use crate::browser::dom_id::DomId;
+use crate::browser::keyboard::Key;
+use crate::browser::selector::Selector;
+use crate::browser::subscription::event_listener::EventListener;
+use crate::browser::subscription::event_listener::EventMatcher;
+use crate::browser::subscription::event_listener::EventPropagation;
+use crate::browser::subscription::event_listener::EventType;
+use crate::browser::subscription::event_listener::ListenTarget;
+use crate::browser::subscription::event_listener::ListenerOptions;
+use crate::browser::subscription::Subscription;
+use crate::browser::subscription::SubscriptionMsg;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chord {
+    pub key: Key,
+    pub ctrl: bool,
+    pub meta: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub primary: bool,
+}
+
+impl Chord {
+    fn has_command_modifier(&self) -> bool {
+        self.ctrl || self.meta || self.alt || self.primary
+    }
+
+    // The chord as matched on one platform, where "mod" is meta on mac and ctrl elsewhere
+    fn resolve_primary(&self, is_mac: bool) -> Chord {
+        Chord {
+            ctrl: self.ctrl || (self.primary && !is_mac),
+            meta: self.meta || (self.primary && is_mac),
+            primary: false,
+            ..self.clone()
+        }
+    }
+
+    fn matcher(&self) -> EventMatcher {
+        EventMatcher::KeyboardKey {
+            key: self.key.clone(),
+            requires_ctrl: self.ctrl,
+            requires_meta: self.meta,
+            requires_alt: self.alt,
+            requires_shift: self.shift,
+            requires_primary: self.primary,
+            exact_modifiers: true,
+            ignore_shift: self.key.is_character(),
+        }
+    }
+
+    fn parse(shortcut: &str, s: &str) -> Result<Chord, ShortcutError> {
+        // The key itself may be a plus sign, i.e. "ctrl++"
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if s == "+" => ("", "+"),
+            None => match s.rsplit_once('+') {
+                Some((modifiers, key)) => (modifiers, key),
+                None => ("", s),
+            },
+        };
+
+        if key.is_empty() {
+            return Err(ShortcutError::MissingKey {
+                shortcut: shortcut.to_string(),
+            });
+        }
+
+        let mut chord = Chord {
+            key: Key::Any,
+            ctrl: false,
+            meta: false,
+            alt: false,
+            shift: false,
+            primary: false,
+        };
+
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "mod" | "primary" => chord.primary = true,
+                "ctrl" | "control" => chord.ctrl = true,
+                "meta" | "cmd" | "command" => chord.meta = true,
+                "alt" | "option" => chord.alt = true,
+                "shift" => chord.shift = true,
+                _ => {
+                    return Err(ShortcutError::UnknownModifier {
+                        shortcut: shortcut.to_string(),
+                        modifier: modifier.to_string(),
+                    })
+                }
+            }
+        }
+
+        chord.key = parse_key(key, chord.shift).map_err(|_| ShortcutError::InvalidKey {
+            shortcut: shortcut.to_string(),
+            key: key.to_string(),
+        })?;
+
+        // Shift is already part of the character, so "shift+a" is the same chord as "A",
+        // while "a" only matches the unshifted key
+        if chord.key.is_character() {
+            chord.shift = false;
+        }
+
+        Ok(chord)
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let modifiers = [
+            (self.primary, "mod"),
+            (self.ctrl, "ctrl"),
+            (self.meta, "meta"),
+            (self.alt, "alt"),
+            (self.shift, "shift"),
+        ];
+
+        for (_, name) in modifiers.iter().filter(|(enabled, _)| *enabled) {
+            write!(f, "{}+", name)?;
+        }
+
+        write!(f, "{}", self.key)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    pub chords: Vec<Chord>,
+}
+
+impl Shortcut {
+    fn is_prefix_of(&self, other: &Shortcut, is_mac: bool) -> bool {
+        self.chords.len() <= other.chords.len()
+            && self
+                .chords
+                .iter()
+                .zip(&other.chords)
+                .all(|(a, b)| a.resolve_primary(is_mac) == b.resolve_primary(is_mac))
+    }
+
+    fn conflicts_with(&self, other: &Shortcut) -> bool {
+        [false, true]
+            .into_iter()
+            .any(|is_mac| self.is_prefix_of(other, is_mac) || other.is_prefix_of(self, is_mac))
+    }
+
+    fn matcher(&self, sequence_timeout: u32) -> EventMatcher {
+        match &self.chords[..] {
+            [chord] => chord.matcher(),
+
+            chords => EventMatcher::KeySequence {
+                steps: chords.iter().map(Chord::matcher).collect(),
+                timeout: sequence_timeout,
+            },
+        }
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+
+        if tokens.is_empty() {
+            return Err(ShortcutError::Empty);
+        }
+
+        let mut chords = vec![];
+
+        for (index, token) in tokens.iter().enumerate() {
+            let expects_chord = index % 2 == 0;
+
+            if expects_chord == (*token == "then") {
+                return Err(ShortcutError::InvalidSequence {
+                    shortcut: s.to_string(),
+                });
+            }
+
+            if expects_chord {
+                chords.push(Chord::parse(s, token)?);
+            }
+        }
+
+        if tokens.last() == Some(&"then") {
+            return Err(ShortcutError::InvalidSequence {
+                shortcut: s.to_string(),
+            });
+        }
+
+        Ok(Shortcut { chords })
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chords: Vec<String> = self.chords.iter().map(|chord| chord.to_string()).collect();
+        write!(f, "{}", chords.join(" then "))
+    }
+}
+
+#[derive(Clone)]
+pub enum ShortcutScope {
+    Document,
+    Element(Selector),
+    Region(Selector),
+}
+
+impl fmt::Display for ShortcutScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShortcutScope::Document => write!(f, "document"),
+            ShortcutScope::Element(selector) => write!(f, "element-{}", selector),
+            ShortcutScope::Region(selector) => write!(f, "region-{}", selector),
+        }
+    }
+}
+
+pub struct Shortcuts<Msg> {
+    scope: ShortcutScope,
+    sequence_timeout: u32,
+    bindings: Vec<(Shortcut, Msg)>,
+}
+
+impl<Msg> Shortcuts<Msg> {
+    pub fn new() -> Shortcuts<Msg> {
+        Shortcuts {
+            scope: ShortcutScope::Document,
+            sequence_timeout: 1000,
+            bindings: vec![],
+        }
+    }
+
+    pub fn scoped_to_element(mut self, id: impl DomId) -> Shortcuts<Msg> {
+        self.scope = ShortcutScope::Element(id.selector());
+        self
+    }
+
+    pub fn scoped_to_region(mut self, id: impl DomId) -> Shortcuts<Msg> {
+        self.scope = ShortcutScope::Region(id.selector());
+        self
+    }
+
+    pub fn scoped_to_selector(mut self, selector: Selector) -> Shortcuts<Msg> {
+        self.scope = ShortcutScope::Region(selector);
+        self
+    }
+
+    pub fn sequence_timeout(mut self, timeout: Duration) -> Shortcuts<Msg> {
+        self.sequence_timeout = timeout.as_millis() as u32;
+        self
+    }
+
+    pub fn bind(mut self, shortcut: &str, msg: Msg) -> Result<Shortcuts<Msg>, ShortcutError> {
+        let shortcut = Shortcut::from_str(shortcut)?;
+
+        for (other, _) in &self.bindings {
+            if shortcut.conflicts_with(other) {
+                return Err(ShortcutError::Conflict {
+                    shortcut: shortcut.to_string(),
+                    other: other.to_string(),
+                });
+            }
+        }
+
+        self.bindings.push((shortcut, msg));
+        Ok(self)
+    }
+
+    pub fn shortcuts(&self) -> Vec<&Shortcut> {
+        self.bindings.iter().map(|(shortcut, _)| shortcut).collect()
+    }
+
+    pub fn into_subscription(self) -> Subscription<Msg> {
+        let listeners = self
+            .bindings
+            .into_iter()
+            .map(|(shortcut, msg)| {
+                Subscription::EventListener(EventListener {
+                    id: format!("shortcut-{}-{}", self.scope, shortcut),
+                    listen_target: ListenTarget::Document,
+                    event_type: EventType::Keydown,
+                    matchers: matchers(&self.scope, &shortcut, self.sequence_timeout),
+                    msg: SubscriptionMsg::pure(msg),
+                    propagation: EventPropagation {
+                        stop_propagation: true,
+                        prevent_default: true,
+                    },
+                    rate_limit: None,
+                    options: ListenerOptions::default(),
+                })
+            })
+            .collect();
+
+        Subscription::Batch(listeners)
+    }
+}
+
+impl<Msg> Default for Shortcuts<Msg> {
+    fn default() -> Self {
+        Shortcuts::new()
+    }
+}
+
+pub fn on_shortcut<Msg>(shortcut: &str, msg: Msg) -> Result<Subscription<Msg>, ShortcutError> {
+    Ok(Shortcuts::new().bind(shortcut, msg)?.into_subscription())
+}
+
+fn matchers(
+    scope: &ShortcutScope,
+    shortcut: &Shortcut,
+    sequence_timeout: u32,
+) -> Vec<EventMatcher> {
+    let mut matchers = vec![];
+
+    match scope {
+        ShortcutScope::Document => {}
+
+        ShortcutScope::Element(selector) => matchers.push(EventMatcher::ExactSelector {
+            selector: selector.clone(),
+        }),
+
+        ShortcutScope::Region(selector) => matchers.push(EventMatcher::ClosestSelector {
+            selector: selector.clone(),
+        }),
+    }
+
+    // Plain keys are ignored while typing, unless the shortcut targets that element
+    let is_element_scope = matches!(scope, ShortcutScope::Element(_));
+    if !is_element_scope && !shortcut.chords[0].has_command_modifier() {
+        matchers.push(EventMatcher::NotEditable);
+    }
+
+    matchers.push(shortcut.matcher(sequence_timeout));
+    matchers
+}
+
+fn parse_key(key: &str, shift: bool) -> Result<Key, <Key as FromStr>::Err> {
+    match Key::from_str(key)? {
+        Key::Character(c) if shift => Ok(Key::Character(c.to_ascii_uppercase())),
+        key => Ok(key),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ShortcutError {
+    Empty,
+    InvalidSequence { shortcut: String },
+    UnknownModifier { shortcut: String, modifier: String },
+    MissingKey { shortcut: String },
+    InvalidKey { shortcut: String, key: String },
+    Conflict { shortcut: String, other: String },
+}
+
+impl fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShortcutError::Empty => write!(f, "empty shortcut"),
+            ShortcutError::InvalidSequence { shortcut } => {
+                write!(f, "invalid sequence in shortcut '{}'", shortcut)
+            }
+            ShortcutError::UnknownModifier { shortcut, modifier } => {
+                write!(
+                    f,
+                    "unknown modifier '{}' in shortcut '{}'",
+                    modifier, shortcut
+                )
+            }
+            ShortcutError::MissingKey { shortcut } => {
+                write!(f, "missing key in shortcut '{}'", shortcut)
+            }
+            ShortcutError::InvalidKey { shortcut, key } => {
+                write!(f, "invalid key '{}' in shortcut '{}'", key, shortcut)
+            }
+            ShortcutError::Conflict { shortcut, other } => {
+                write!(f, "shortcut '{}' conflicts with '{}'", shortcut, other)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShortcutError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(key: Key) -> Chord {
+        Chord {
+            key,
+            ctrl: false,
+            meta: false,
+            alt: false,
+            shift: false,
+            primary: false,
+        }
+    }
+
+    fn parse(shortcut: &str) -> Result<Shortcut, ShortcutError> {
+        Shortcut::from_str(shortcut)
+    }
+
+    #[test]
+    fn parse_modifiers() {
+        assert_eq!(
+            parse("mod+k").unwrap().chords,
+            vec![Chord {
+                primary: true,
+                ..chord(Key::Character('k'))
+            }]
+        );
+
+        assert_eq!(
+            parse("Ctrl+Alt+Shift+Enter").unwrap().chords,
+            vec![Chord {
+                ctrl: true,
+                alt: true,
+                shift: true,
+                ..chord(Key::Enter)
+            }]
+        );
+
+        assert_eq!(
+            parse("ctrl++").unwrap().chords,
+            vec![Chord {
+                ctrl: true,
+                ..chord(Key::Character('+'))
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_sequences() {
+        assert_eq!(
+            parse("g then i").unwrap().chords,
+            vec![chord(Key::Character('g')), chord(Key::Character('i'))]
+        );
+
+        assert_eq!(parse("g then i").unwrap().to_string(), "g then i");
+    }
+
+    #[test]
+    fn parse_folds_shift_into_characters() {
+        assert_eq!(parse("shift+?").unwrap(), parse("?").unwrap());
+        assert_eq!(
+            parse("shift+a").unwrap().chords,
+            vec![chord(Key::Character('A'))]
+        );
+        assert_eq!(parse("shift+a").unwrap(), parse("A").unwrap());
+        assert_ne!(parse("a").unwrap(), parse("A").unwrap());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse(" "), Err(ShortcutError::Empty));
+
+        assert_eq!(
+            parse("hyper+k"),
+            Err(ShortcutError::UnknownModifier {
+                shortcut: "hyper+k".to_string(),
+                modifier: "hyper".to_string(),
+            })
+        );
+
+        assert_eq!(
+            parse("g then"),
+            Err(ShortcutError::InvalidSequence {
+                shortcut: "g then".to_string(),
+            })
+        );
+
+        assert_eq!(
+            parse("g i"),
+            Err(ShortcutError::InvalidSequence {
+                shortcut: "g i".to_string(),
+            })
+        );
+
+        assert_eq!(
+            parse("ctrl+enterr"),
+            Err(ShortcutError::InvalidKey {
+                shortcut: "ctrl+enterr".to_string(),
+                key: "enterr".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn character_keys_ignore_shift() {
+        match parse("?").unwrap().matcher(1000) {
+            EventMatcher::KeyboardKey {
+                requires_shift,
+                exact_modifiers,
+                ignore_shift,
+                ..
+            } => assert!(!requires_shift && exact_modifiers && ignore_shift),
+            _ => panic!("Expected a keyboard key matcher"),
+        }
+
+        match parse("shift+enter").unwrap().matcher(1000) {
+            EventMatcher::KeyboardKey {
+                requires_shift,
+                ignore_shift,
+                ..
+            } => assert!(requires_shift && !ignore_shift),
+            _ => panic!("Expected a keyboard key matcher"),
+        }
+    }
+
+    #[test]
+    fn bind_rejects_invalid_and_conflicting_shortcuts() {
+        assert!(Shortcuts::new().bind("ctrl+", ()).is_err());
+
+        let conflict = Shortcuts::new()
+            .bind("g then i", 1)
+            .and_then(|shortcuts| shortcuts.bind("g", 2));
+
+        assert_eq!(
+            conflict.err(),
+            Some(ShortcutError::Conflict {
+                shortcut: "g".to_string(),
+                other: "g then i".to_string(),
+            })
+        );
+
+        let shortcuts = Shortcuts::new()
+            .bind("g then i", 1)
+            .and_then(|shortcuts| shortcuts.bind("g then p", 2))
+            .unwrap();
+
+        assert_eq!(shortcuts.shortcuts().len(), 2);
+        assert_eq!(shortcuts.into_subscription().into_vec().len(), 2);
+    }
+
+    #[test]
+    fn bind_resolves_primary_on_both_platforms() {
+        let bind = |a, b| Shortcuts::new().bind(a, 1).and_then(|s| s.bind(b, 2));
+
+        assert!(bind("mod+k", "ctrl+k").is_err());
+        assert!(bind("mod+k", "meta+k").is_err());
+        assert!(bind("ctrl+k", "meta+k").is_ok());
+        assert!(bind("mod+k", "mod+shift+k").is_ok());
+    }
+}
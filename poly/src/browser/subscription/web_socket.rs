@@ -0,0 +1,140 @@
+use crate::browser::subscription::Subscription;
+use crate::browser::subscription::SubscriptionMsg;
+use crate::browser::value::Capture;
+use std::time::Duration;
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSocket<Msg> {
+    pub id: String,
+    pub url: String,
+    pub protocols: Vec<String>,
+    pub message_format: MessageFormat,
+    pub on_message: SubscriptionMsg<Msg>,
+    pub on_open: Option<SubscriptionMsg<Msg>>,
+    pub on_close: Option<SubscriptionMsg<Msg>>,
+    pub on_error: Option<SubscriptionMsg<Msg>>,
+    pub reconnect: Option<ReconnectConfig>,
+}
+
+impl<Msg> WebSocket<Msg> {
+    pub fn new<ToMsg, T>(id: &str, url: &str, to_msg: ToMsg) -> WebSocket<Msg>
+    where
+        ToMsg: Fn(Capture<T>) -> Msg,
+        T: Default,
+    {
+        WebSocket {
+            id: id.to_string(),
+            url: url.to_string(),
+            protocols: vec![],
+            message_format: MessageFormat::Json,
+            on_message: SubscriptionMsg::pure(to_msg(Default::default())),
+            on_open: None,
+            on_close: None,
+            on_error: None,
+            reconnect: None,
+        }
+    }
+
+    pub(crate) fn map_ref<B>(self, to_msg: &dyn Fn(Msg) -> B) -> WebSocket<B> {
+        WebSocket {
+            id: self.id,
+            url: self.url,
+            protocols: self.protocols,
+            message_format: self.message_format,
+            on_message: self.on_message.map_ref(to_msg),
+            on_open: self.on_open.map(|msg| msg.map_ref(to_msg)),
+            on_close: self.on_close.map(|msg| msg.map_ref(to_msg)),
+            on_error: self.on_error.map(|msg| msg.map_ref(to_msg)),
+            reconnect: self.reconnect,
+        }
+    }
+
+    pub fn protocols(mut self, protocols: &[&str]) -> WebSocket<Msg> {
+        self.protocols = protocols.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    pub fn text_messages(mut self) -> WebSocket<Msg> {
+        self.message_format = MessageFormat::Text;
+        self
+    }
+
+    pub fn json_messages(mut self) -> WebSocket<Msg> {
+        self.message_format = MessageFormat::Json;
+        self
+    }
+
+    pub fn on_open(mut self, msg: Msg) -> WebSocket<Msg> {
+        self.on_open = Some(SubscriptionMsg::pure(msg));
+        self
+    }
+
+    pub fn on_close<ToMsg>(mut self, to_msg: ToMsg) -> WebSocket<Msg>
+    where
+        ToMsg: Fn(Capture<CloseInfo>) -> Msg,
+    {
+        self.on_close = Some(SubscriptionMsg::pure(to_msg(Default::default())));
+        self
+    }
+
+    pub fn on_error(mut self, msg: Msg) -> WebSocket<Msg> {
+        self.on_error = Some(SubscriptionMsg::pure(msg));
+        self
+    }
+
+    pub fn reconnect(mut self, config: ReconnectConfig) -> WebSocket<Msg> {
+        self.reconnect = Some(config);
+        self
+    }
+}
+
+pub fn web_socket<Msg>(config: WebSocket<Msg>) -> Subscription<Msg> {
+    Subscription::WebSocket(Box::new(config))
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MessageFormat {
+    Json,
+    Text,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconnectConfig {
+    pub initial_delay: u64,
+    pub max_delay: u64,
+    pub multiplier: f64,
+    pub max_attempts: Option<u32>,
+}
+
+impl ReconnectConfig {
+    pub fn exponential(initial_delay: Duration, max_delay: Duration) -> ReconnectConfig {
+        ReconnectConfig {
+            initial_delay: initial_delay.as_millis() as u64,
+            max_delay: max_delay.as_millis() as u64,
+            multiplier: 2.0,
+            max_attempts: None,
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> ReconnectConfig {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        ReconnectConfig::exponential(Duration::from_secs(1), Duration::from_secs(30))
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloseInfo {
+    pub code: u16,
+    pub reason: String,
+    pub was_clean: bool,
+}
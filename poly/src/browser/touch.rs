@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Touch {
+    pub identifier: i64,
+    pub client_x: f64,
+    pub client_y: f64,
+    pub page_x: f64,
+    pub page_y: f64,
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchInfo {
+    pub touches: Vec<Touch>,
+    pub target_touches: Vec<Touch>,
+    pub changed_touches: Vec<Touch>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SwipeDirection {
+    #[default]
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Swipe {
+    pub direction: SwipeDirection,
+    pub distance: f64,
+    pub duration: u64,
+    pub velocity: f64,
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LongPress {
+    pub client_x: f64,
+    pub client_y: f64,
+    pub duration: u64,
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pinch {
+    pub scale: f64,
+    pub center_x: f64,
+    pub center_y: f64,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwipeConfig {
+    min_distance: u32,
+    max_duration: u32,
+    directions: Vec<SwipeDirection>,
+}
+
+impl SwipeConfig {
+    pub fn new() -> SwipeConfig {
+        SwipeConfig {
+            min_distance: 30,
+            max_duration: 500,
+            directions: vec![
+                SwipeDirection::Left,
+                SwipeDirection::Right,
+                SwipeDirection::Up,
+                SwipeDirection::Down,
+            ],
+        }
+    }
+
+    pub fn min_distance(mut self, pixels: u32) -> SwipeConfig {
+        self.min_distance = pixels;
+        self
+    }
+
+    pub fn max_duration(mut self, duration: Duration) -> SwipeConfig {
+        self.max_duration = duration.as_millis() as u32;
+        self
+    }
+
+    pub fn directions(mut self, directions: Vec<SwipeDirection>) -> SwipeConfig {
+        self.directions = directions;
+        self
+    }
+}
+
+impl Default for SwipeConfig {
+    fn default() -> Self {
+        SwipeConfig::new()
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LongPressConfig {
+    delay: u32,
+    tolerance: u32,
+}
+
+impl LongPressConfig {
+    pub fn new(delay: Duration) -> LongPressConfig {
+        LongPressConfig {
+            delay: delay.as_millis() as u32,
+            tolerance: 10,
+        }
+    }
+
+    pub fn tolerance(mut self, pixels: u32) -> LongPressConfig {
+        self.tolerance = pixels;
+        self
+    }
+}
+
+impl Default for LongPressConfig {
+    fn default() -> Self {
+        LongPressConfig::new(Duration::from_millis(500))
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
+pub(crate) const CAPTURE_VALUE: &str = "$CAPTURE_VALUE";
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Value(serde_json::Value);
 
@@ -67,7 +69,7 @@ impl<T> Serialize for Capture<T> {
     where
         S: Serializer,
     {
-        serializer.serialize_str("$CAPTURE_VALUE")
+        serializer.serialize_str(CAPTURE_VALUE)
     }
 }
 
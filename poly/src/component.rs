@@ -0,0 +1,49 @@
+use crate::browser::effect::Effect;
+use crate::browser::subscription::Subscription;
+
+pub trait Component<Model, Msg, Markup> {
+    type Error;
+
+    fn init(&self) -> Result<(Model, Effect<Msg>), Self::Error>;
+    fn subscriptions(&self, model: &Model) -> Subscription<Msg>;
+    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effect<Msg>, Self::Error>;
+    fn view(&self, model: &Model) -> Markup;
+}
+
+pub fn init<C, Model, Msg, Markup, ToMsg, ParentMsg>(
+    component: &C,
+    to_msg: ToMsg,
+) -> Result<(Model, Effect<ParentMsg>), C::Error>
+where
+    C: Component<Model, Msg, Markup>,
+    ToMsg: Fn(Msg) -> ParentMsg,
+{
+    let (model, effect) = component.init()?;
+    Ok((model, effect.map(to_msg)))
+}
+
+pub fn subscriptions<C, Model, Msg, Markup, ToMsg, ParentMsg>(
+    component: &C,
+    model: &Model,
+    to_msg: ToMsg,
+) -> Subscription<ParentMsg>
+where
+    C: Component<Model, Msg, Markup>,
+    ToMsg: Fn(Msg) -> ParentMsg,
+{
+    component.subscriptions(model).map(to_msg)
+}
+
+pub fn update<C, Model, Msg, Markup, ToMsg, ParentMsg>(
+    component: &C,
+    msg: &Msg,
+    model: &mut Model,
+    to_msg: ToMsg,
+) -> Result<Effect<ParentMsg>, C::Error>
+where
+    C: Component<Model, Msg, Markup>,
+    ToMsg: Fn(Msg) -> ParentMsg,
+{
+    let effect = component.update(msg, model)?;
+    Ok(effect.map(to_msg))
+}
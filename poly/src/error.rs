@@ -0,0 +1,94 @@
+use std::fmt;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum Error {
+    #[serde(rename_all = "camelCase")]
+    DecodeModel {
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    DecodeMsg {
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Encode {
+        value: String,
+        message: String,
+    },
+    ModelNotInitialized,
+    #[serde(rename_all = "camelCase")]
+    User {
+        code: String,
+        message: String,
+        context: Option<serde_json::Value>,
+    },
+}
+
+impl Error {
+    pub fn user(code: &str, message: &str) -> Error {
+        Error::User {
+            code: code.to_string(),
+            message: message.to_string(),
+            context: None,
+        }
+    }
+
+    pub fn with_context<C>(self, context: C) -> Error
+    where
+        C: serde::Serialize,
+    {
+        match self {
+            Error::User { code, message, .. } => Error::User {
+                code,
+                message,
+                context: serde_json::to_value(context).ok(),
+            },
+
+            _ => self,
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            Error::DecodeModel { .. } => "decode_model",
+            Error::DecodeMsg { .. } => "decode_msg",
+            Error::Encode { .. } => "encode",
+            Error::ModelNotInitialized => "model_not_initialized",
+            Error::User { code, .. } => code,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DecodeModel { message } => write!(f, "Failed to decode model: {}", message),
+            Error::DecodeMsg { message } => write!(f, "Failed to decode msg: {}", message),
+            Error::Encode { value, message } => {
+                write!(f, "Failed to encode {}: {}", value, message)
+            }
+            Error::ModelNotInitialized => write!(f, "Model is not initialized"),
+            Error::User { code, message, .. } => write!(f, "{}: {}", code, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::User {
+            code: "error".to_string(),
+            message,
+            context: None,
+        }
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::from(message.to_string())
+    }
+}
@@ -0,0 +1,368 @@
+use crate::markup::syntax::escape_attribute;
+use crate::markup::syntax::escape_text;
+
+#[derive(Clone, Debug, Default)]
+pub struct Head {
+    pub title: Option<String>,
+    pub meta: Vec<Meta>,
+    pub links: Vec<Link>,
+    pub scripts: Vec<Script>,
+}
+
+impl Head {
+    pub fn new() -> Head {
+        Head::default()
+    }
+
+    pub fn title(mut self, title: &str) -> Head {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn description(self, description: &str) -> Head {
+        self.meta(Meta::name("description", description))
+    }
+
+    pub fn canonical(self, url: &str) -> Head {
+        self.link(Link::new("canonical", url))
+    }
+
+    pub fn og_title(self, title: &str) -> Head {
+        self.meta(Meta::property("og:title", title))
+    }
+
+    pub fn og_description(self, description: &str) -> Head {
+        self.meta(Meta::property("og:description", description))
+    }
+
+    pub fn og_image(self, url: &str) -> Head {
+        self.meta(Meta::property("og:image", url))
+    }
+
+    pub fn og_url(self, url: &str) -> Head {
+        self.meta(Meta::property("og:url", url))
+    }
+
+    pub fn og_type(self, type_: &str) -> Head {
+        self.meta(Meta::property("og:type", type_))
+    }
+
+    pub fn og_site_name(self, name: &str) -> Head {
+        self.meta(Meta::property("og:site_name", name))
+    }
+
+    pub fn twitter_card(self, card: TwitterCard) -> Head {
+        self.meta(Meta::name("twitter:card", card.as_str()))
+    }
+
+    pub fn twitter_site(self, handle: &str) -> Head {
+        self.meta(Meta::name("twitter:site", handle))
+    }
+
+    pub fn twitter_title(self, title: &str) -> Head {
+        self.meta(Meta::name("twitter:title", title))
+    }
+
+    pub fn twitter_description(self, description: &str) -> Head {
+        self.meta(Meta::name("twitter:description", description))
+    }
+
+    pub fn twitter_image(self, url: &str) -> Head {
+        self.meta(Meta::name("twitter:image", url))
+    }
+
+    pub fn stylesheet(self, stylesheet: Stylesheet) -> Head {
+        self.link(stylesheet.into_link())
+    }
+
+    pub fn script(mut self, script: Script) -> Head {
+        self.scripts.push(script);
+        self
+    }
+
+    pub fn meta(mut self, meta: Meta) -> Head {
+        self.meta.push(meta);
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Head {
+        self.links.push(link);
+        self
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+
+        if let Some(title) = &self.title {
+            html.push_str(&format!("<title>{}</title>", escape_text(title)));
+        }
+
+        for meta in &self.meta {
+            html.push_str(&meta.to_html());
+        }
+
+        for link in &self.links {
+            html.push_str(&link.to_html());
+        }
+
+        for script in &self.scripts {
+            html.push_str(&script.to_html());
+        }
+
+        html
+    }
+}
+
+#[cfg(feature = "maud")]
+impl maud::Render for Head {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(&self.to_html());
+    }
+}
+
+#[cfg(feature = "html")]
+impl From<Head> for crate::markup::html::Html {
+    fn from(head: Head) -> Self {
+        crate::markup::html::raw(&head.to_html())
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum Meta {
+    #[serde(rename_all = "camelCase")]
+    Name { name: String, content: String },
+    #[serde(rename_all = "camelCase")]
+    Property { property: String, content: String },
+}
+
+impl Meta {
+    pub fn name(name: &str, content: &str) -> Meta {
+        Meta::Name {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    pub fn property(property: &str, content: &str) -> Meta {
+        Meta::Property {
+            property: property.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        match self {
+            Meta::Name { name, content } => format!(
+                r#"<meta name="{}" content="{}">"#,
+                escape_attribute(name),
+                escape_attribute(content)
+            ),
+
+            Meta::Property { property, content } => format!(
+                r#"<meta property="{}" content="{}">"#,
+                escape_attribute(property),
+                escape_attribute(content)
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum TwitterCard {
+    Summary,
+    SummaryLargeImage,
+    App,
+    Player,
+}
+
+impl TwitterCard {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TwitterCard::Summary => "summary",
+            TwitterCard::SummaryLargeImage => "summary_large_image",
+            TwitterCard::App => "app",
+            TwitterCard::Player => "player",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Link {
+    pub rel: String,
+    pub href: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Link {
+    pub fn new(rel: &str, href: &str) -> Link {
+        Link {
+            rel: rel.to_string(),
+            href: href.to_string(),
+            attributes: vec![],
+        }
+    }
+
+    pub fn attribute(mut self, name: &str, value: &str) -> Link {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn to_html(&self) -> String {
+        format!(
+            r#"<link rel="{}" href="{}"{}>"#,
+            escape_attribute(&self.rel),
+            escape_attribute(&self.href),
+            attributes_html(&self.attributes)
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Stylesheet {
+    pub href: String,
+    pub integrity: Option<String>,
+    pub crossorigin: Option<String>,
+    pub media: Option<String>,
+}
+
+impl Stylesheet {
+    pub fn new(href: &str) -> Stylesheet {
+        Stylesheet {
+            href: href.to_string(),
+            integrity: None,
+            crossorigin: None,
+            media: None,
+        }
+    }
+
+    pub fn integrity(mut self, integrity: &str) -> Stylesheet {
+        self.integrity = Some(integrity.to_string());
+        self
+    }
+
+    pub fn crossorigin(mut self, crossorigin: &str) -> Stylesheet {
+        self.crossorigin = Some(crossorigin.to_string());
+        self
+    }
+
+    pub fn media(mut self, media: &str) -> Stylesheet {
+        self.media = Some(media.to_string());
+        self
+    }
+
+    fn into_link(self) -> Link {
+        let mut link = Link::new("stylesheet", &self.href);
+
+        for (name, value) in [
+            ("integrity", self.integrity),
+            ("crossorigin", self.crossorigin),
+            ("media", self.media),
+        ] {
+            if let Some(value) = value {
+                link = link.attribute(name, &value);
+            }
+        }
+
+        link
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Script {
+    pub src: String,
+    pub integrity: Option<String>,
+    pub nonce: Option<String>,
+    pub crossorigin: Option<String>,
+    pub module: bool,
+    pub defer: bool,
+    pub async_: bool,
+}
+
+impl Script {
+    pub fn new(src: &str) -> Script {
+        Script {
+            src: src.to_string(),
+            integrity: None,
+            nonce: None,
+            crossorigin: None,
+            module: false,
+            defer: false,
+            async_: false,
+        }
+    }
+
+    pub fn integrity(mut self, integrity: &str) -> Script {
+        self.integrity = Some(integrity.to_string());
+        self
+    }
+
+    pub fn nonce(mut self, nonce: &str) -> Script {
+        self.nonce = Some(nonce.to_string());
+        self
+    }
+
+    pub fn crossorigin(mut self, crossorigin: &str) -> Script {
+        self.crossorigin = Some(crossorigin.to_string());
+        self
+    }
+
+    pub fn module(mut self) -> Script {
+        self.module = true;
+        self
+    }
+
+    pub fn defer(mut self) -> Script {
+        self.defer = true;
+        self
+    }
+
+    pub fn async_(mut self) -> Script {
+        self.async_ = true;
+        self
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut attributes = vec![("src".to_string(), self.src.clone())];
+
+        if self.module {
+            attributes.push(("type".to_string(), "module".to_string()));
+        }
+
+        for (name, value) in [
+            ("integrity", &self.integrity),
+            ("nonce", &self.nonce),
+            ("crossorigin", &self.crossorigin),
+        ] {
+            if let Some(value) = value {
+                attributes.push((name.to_string(), value.clone()));
+            }
+        }
+
+        let mut flags = String::new();
+
+        if self.defer {
+            flags.push_str(" defer");
+        }
+
+        if self.async_ {
+            flags.push_str(" async");
+        }
+
+        format!("<script{}{}></script>", attributes_html(&attributes), flags)
+    }
+}
+
+fn attributes_html(attributes: &[(String, String)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                r#" {}="{}""#,
+                escape_attribute(name),
+                escape_attribute(value)
+            )
+        })
+        .collect()
+}
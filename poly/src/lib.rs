@@ -1,5 +1,16 @@
 pub mod asset;
 pub mod browser;
+pub mod component;
+pub mod error;
+pub mod head;
+pub mod markup;
 pub mod page;
 pub mod route;
+pub mod router;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod time;
+#[cfg(feature = "vdom")]
+pub mod vdom;
+
+pub use error::Error;
@@ -0,0 +1,34 @@
+#[cfg(feature = "html")]
+pub mod html;
+pub(crate) mod syntax;
+
+use crate::page::PageMarkup;
+
+pub trait Markup: Sized {
+    fn render(self) -> String;
+
+    fn render_page(markup: PageMarkup<Self>) -> String {
+        format!(
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8">{}</head><body>{}</body></html>"#,
+            markup.head.render(),
+            markup.body.render()
+        )
+    }
+}
+
+#[cfg(feature = "maud")]
+impl Markup for maud::Markup {
+    fn render(self) -> String {
+        self.into_string()
+    }
+
+    fn render_page(markup: PageMarkup<Self>) -> String {
+        crate::page::render_page_maud(markup)
+    }
+}
+
+impl Markup for String {
+    fn render(self) -> String {
+        self
+    }
+}
@@ -0,0 +1,187 @@
+use crate::browser::dom_id::DomId;
+use crate::markup::syntax::escape_attribute;
+use crate::markup::syntax::escape_raw_text;
+use crate::markup::syntax::escape_text;
+use crate::markup::syntax::RAW_TEXT_ELEMENTS;
+use crate::markup::syntax::VOID_ELEMENTS;
+use crate::markup::Markup;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Html {
+    Element(Element),
+    Text(String),
+    Raw(String),
+    Fragment(Vec<Html>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Element {
+    tag: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Html>,
+}
+
+impl Element {
+    pub fn new(tag: &str) -> Element {
+        Element {
+            tag: tag.to_string(),
+            attributes: vec![],
+            children: vec![],
+        }
+    }
+
+    pub fn attr(mut self, name: &str, value: &str) -> Element {
+        match self.attributes.iter_mut().find(|(key, _)| key == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.attributes.push((name.to_string(), value.to_string())),
+        }
+
+        self
+    }
+
+    pub fn bool_attr(self, name: &str, enabled: bool) -> Element {
+        if enabled {
+            self.attr(name, "")
+        } else {
+            self
+        }
+    }
+
+    pub fn id(self, id: impl DomId) -> Element {
+        self.attr("id", &id.to_string())
+    }
+
+    pub fn class(self, class: &str) -> Element {
+        let classes = match self.attribute("class") {
+            Some(existing) if !existing.is_empty() => format!("{} {}", existing, class),
+            _ => class.to_string(),
+        };
+
+        self.attr("class", &classes)
+    }
+
+    pub fn child(mut self, child: impl Into<Html>) -> Element {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn children<I, H>(mut self, children: I) -> Element
+    where
+        I: IntoIterator<Item = H>,
+        H: Into<Html>,
+    {
+        self.children.extend(children.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn text(self, text: &str) -> Element {
+        self.child(Html::Text(text.to_string()))
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn write(&self, html: &mut String) {
+        html.push('<');
+        html.push_str(&self.tag);
+
+        for (name, value) in &self.attributes {
+            if value.is_empty() {
+                html.push_str(&format!(" {}", name));
+            } else {
+                html.push_str(&format!(" {}=\"{}\"", name, escape_attribute(value)));
+            }
+        }
+
+        html.push('>');
+
+        if VOID_ELEMENTS.contains(&self.tag.as_str()) {
+            return;
+        }
+
+        // noscript is only raw text when scripting is enabled, so its text is escaped as usual
+        let raw_text = RAW_TEXT_ELEMENTS.contains(&self.tag.as_str()) && self.tag != "noscript";
+
+        for child in &self.children {
+            match child {
+                Html::Text(text) if raw_text => html.push_str(&escape_raw_text(&self.tag, text)),
+                _ => child.write(html),
+            }
+        }
+
+        html.push_str(&format!("</{}>", self.tag));
+    }
+}
+
+impl Html {
+    fn write(&self, html: &mut String) {
+        match self {
+            Html::Element(element) => element.write(html),
+            Html::Text(text) => html.push_str(&escape_text(text)),
+            Html::Raw(raw) => html.push_str(raw),
+            Html::Fragment(children) => children.iter().for_each(|child| child.write(html)),
+        }
+    }
+}
+
+impl Markup for Html {
+    fn render(self) -> String {
+        let mut html = String::new();
+        self.write(&mut html);
+        html
+    }
+}
+
+impl From<Element> for Html {
+    fn from(element: Element) -> Self {
+        Html::Element(element)
+    }
+}
+
+impl From<&str> for Html {
+    fn from(text: &str) -> Self {
+        Html::Text(text.to_string())
+    }
+}
+
+impl From<String> for Html {
+    fn from(text: String) -> Self {
+        Html::Text(text)
+    }
+}
+
+pub fn text(text: &str) -> Html {
+    Html::Text(text.to_string())
+}
+
+pub fn raw(html: &str) -> Html {
+    Html::Raw(html.to_string())
+}
+
+pub fn fragment<I, H>(children: I) -> Html
+where
+    I: IntoIterator<Item = H>,
+    H: Into<Html>,
+{
+    Html::Fragment(children.into_iter().map(Into::into).collect())
+}
+
+macro_rules! elements {
+    ($($name:ident),* $(,)?) => {
+        $(
+            pub fn $name() -> Element {
+                Element::new(stringify!($name))
+            }
+        )*
+    };
+}
+
+elements!(
+    a, article, aside, br, button, code, div, em, footer, form, h1, h2, h3, h4, h5, h6, header, hr,
+    img, input, label, li, link, main, meta, nav, ol, option, p, pre, script, section, select,
+    span, strong, style, table, tbody, td, textarea, th, thead, title, tr, ul,
+);
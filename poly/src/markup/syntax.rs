@@ -0,0 +1,85 @@
+#[cfg(any(feature = "html", feature = "vdom"))]
+pub(crate) const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+#[cfg(any(feature = "html", feature = "vdom"))]
+pub(crate) const RAW_TEXT_ELEMENTS: &[&str] = &[
+    "iframe", "noembed", "noframes", "noscript", "script", "style", "xmp",
+];
+
+#[cfg(feature = "vdom")]
+pub(crate) const ESCAPABLE_RAW_TEXT_ELEMENTS: &[&str] = &["textarea", "title"];
+
+pub(crate) fn escape_text(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+pub(crate) fn escape_attribute(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Raw text can't contain its own end tag, so "</script" becomes "<\/script", which is
+// equivalent inside js strings, regexes and comments, and inside css strings.
+// In scripts "<!--" is escaped the same way, since it changes how the end tag is found.
+#[cfg(feature = "html")]
+pub(crate) fn escape_raw_text(tag: &str, s: &str) -> String {
+    let lowercase = s.to_ascii_lowercase();
+    let end_tag = format!("</{}", tag);
+    let mut escaped = String::with_capacity(s.len());
+    let mut index = 0;
+
+    while index < s.len() {
+        let rest = &lowercase[index..];
+
+        if rest.starts_with(&end_tag) {
+            escaped.push_str("<\\/");
+            index += 2;
+        } else if tag == "script" && rest.starts_with("<!--") {
+            escaped.push_str("<\\!");
+            index += 2;
+        } else {
+            let c = s[index..].chars().next().unwrap_or_default();
+            escaped.push(c);
+            index += c.len_utf8();
+        }
+    }
+
+    escaped
+}
+
+#[cfg(all(test, feature = "html"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_raw_text_breaks_end_tags() {
+        assert_eq!(
+            escape_raw_text("script", "let s = '</script><script>alert(1)</SCRIPT>';"),
+            "let s = '<\\/script><script>alert(1)<\\/SCRIPT>';"
+        );
+
+        assert_eq!(
+            escape_raw_text("script", "'<!--<script>'"),
+            "'<\\!--<script>'"
+        );
+
+        assert_eq!(
+            escape_raw_text("style", "a::after { content: '</style>' }"),
+            "a::after { content: '<\\/style>' }"
+        );
+    }
+
+    #[test]
+    fn escape_raw_text_keeps_other_text() {
+        let script = "if (a < b && c > d) { x = '</div>' }";
+        assert_eq!(escape_raw_text("script", script), script);
+        assert_eq!(escape_raw_text("style", "<!-- ü -->"), "<!-- ü -->");
+    }
+}
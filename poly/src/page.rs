@@ -1,24 +1,52 @@
+pub mod native;
+#[cfg(feature = "wasm")]
 pub mod wasm;
 
 use crate::browser::dom_id::DomId;
 use crate::browser::effect;
 use crate::browser::effect::Effect;
 use crate::browser::subscription::Subscription;
+use crate::error::Error;
+use crate::markup;
+#[cfg(feature = "maud")]
 use maud::html;
+use serde::Deserialize;
+
+pub trait Page<Model, Msg, Markup>
+where
+    Markup: markup::Markup,
+{
+    type Error: Into<Error>;
 
-pub trait Page<Model, Msg, Markup> {
     fn id(&self) -> &'static dyn DomId;
-    fn init(&self) -> Result<(Model, Effect<Msg>), String>;
+    fn init(&self) -> Result<(Model, Effect<Msg>), Self::Error>;
     fn subscriptions(&self, model: &Model) -> Subscription<Msg>;
-    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effect<Msg>, String>;
-    fn update_from_js(&self, _msg: JsMsg, _model: &mut Model) -> Result<Effect<Msg>, String> {
+    fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effect<Msg>, Self::Error>;
+    fn update_from_js(&self, _msg: JsMsg, _model: &mut Model) -> Result<Effect<Msg>, Self::Error> {
         Ok(effect::none())
     }
     fn view(&self, model: &Model) -> PageMarkup<Markup>;
-    fn render(&self, markup: Markup) -> String;
-    fn render_page(&self, markup: PageMarkup<Markup>) -> String;
+    fn render(&self, markup: Markup) -> String {
+        markup.render()
+    }
+    fn render_page(&self, markup: PageMarkup<Markup>) -> String {
+        Markup::render_page(markup)
+    }
+    fn model_migration(&self) -> Option<&dyn ModelMigration<Model>> {
+        None
+    }
+    fn render_page_with_state(&self, markup: PageMarkup<Markup>, state: &PageState) -> String {
+        insert_state_script(self.render_page(markup), state)
+    }
+}
+
+pub trait ModelMigration<Model> {
+    fn version(&self) -> u32;
+    fn migrate(&self, from_version: u32, model: serde_json::Value) -> Result<Model, Error>;
 }
 
+pub const STATE_ELEMENT_ID: &str = "poly-state";
+
 #[derive(Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JsMsg {
@@ -27,11 +55,132 @@ pub struct JsMsg {
     pub data: serde_json::Value,
 }
 
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelAndEffects<Model, Msg> {
+    pub model: Model,
+    pub effects: Vec<Effect<Msg>>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionedModel<Model> {
+    pub v: u32,
+    pub model: Model,
+}
+
+pub fn encode_model<P, Model, Msg, Markup>(
+    page: &P,
+    model: &Model,
+) -> Result<serde_json::Value, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+{
+    let value = match page.model_migration() {
+        Some(migration) => serde_json::to_value(VersionedModel {
+            v: migration.version(),
+            model,
+        }),
+
+        None => serde_json::to_value(model),
+    };
+
+    value.map_err(|err| Error::Encode {
+        value: "model".to_string(),
+        message: err.to_string(),
+    })
+}
+
+pub fn decode_model<P, Model, Msg, Markup>(
+    page: &P,
+    value: serde_json::Value,
+) -> Result<Model, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+{
+    let deserialize = |model| {
+        serde_json::from_value(model).map_err(|err| Error::DecodeModel {
+            message: err.to_string(),
+        })
+    };
+
+    let migration = match page.model_migration() {
+        Some(migration) => migration,
+        None => return deserialize(value),
+    };
+
+    let current_version = migration.version();
+
+    // Pages with a migration always write the envelope, so only models stored before
+    // the migration was added are bare. Those are treated as version 0.
+    let versioned = match VersionedModel::<serde_json::Value>::deserialize(&value) {
+        Ok(versioned) => versioned,
+        Err(_) => VersionedModel { v: 0, model: value },
+    };
+
+    if versioned.v == current_version {
+        deserialize(versioned.model)
+    } else if versioned.v < current_version {
+        migration.migrate(versioned.v, versioned.model)
+    } else {
+        Err(Error::DecodeModel {
+            message: format!(
+                "Model version {} is newer than the current version {}",
+                versioned.v, current_version
+            ),
+        })
+    }
+}
+
+pub struct PageState {
+    json: String,
+}
+
+impl PageState {
+    pub fn new<Model, Msg>(model_and_effects: &ModelAndEffects<Model, Msg>) -> Result<Self, Error>
+    where
+        Model: serde::Serialize,
+        Msg: serde::Serialize,
+    {
+        let json = serde_json::to_string(model_and_effects).map_err(|err| Error::Encode {
+            value: "page state".to_string(),
+            message: err.to_string(),
+        })?;
+
+        // Make the json safe to embed in a script tag
+        Ok(PageState {
+            json: json.replace('<', "\\u003c"),
+        })
+    }
+
+    pub fn from_model<Model>(model: Model) -> Result<Self, Error>
+    where
+        Model: serde::Serialize,
+    {
+        PageState::new::<Model, ()>(&ModelAndEffects {
+            model,
+            effects: vec![],
+        })
+    }
+
+    pub fn script(&self) -> String {
+        format!(
+            r#"<script type="application/json" id="{}">{}</script>"#,
+            STATE_ELEMENT_ID, self.json
+        )
+    }
+}
+
 pub struct PageMarkup<Html> {
     pub head: Html,
     pub body: Html,
 }
 
+#[cfg(feature = "maud")]
 pub fn render_page_maud(markup: PageMarkup<maud::Markup>) -> String {
     (html! {
         (maud::DOCTYPE)
@@ -47,3 +196,277 @@ pub fn render_page_maud(markup: PageMarkup<maud::Markup>) -> String {
     })
     .into_string()
 }
+
+#[cfg(feature = "maud")]
+pub fn render_page_maud_with_state(markup: PageMarkup<maud::Markup>, state: &PageState) -> String {
+    (html! {
+        (maud::DOCTYPE)
+        html {
+            head {
+                meta charset="utf-8";
+                (markup.head)
+                (maud::PreEscaped(state.script()))
+            }
+            body {
+                (markup.body)
+            }
+        }
+    })
+    .into_string()
+}
+
+fn insert_state_script(html: String, state: &PageState) -> String {
+    match html.find("</head>") {
+        Some(index) => format!("{}{}{}", &html[..index], state.script(), &html[index..]),
+        None => format!("{}{}", state.script(), html),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::subscription;
+    use crate::head::Head;
+    use serde_json::json;
+    use std::fmt;
+
+    struct Id;
+
+    impl fmt::Display for Id {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "test-page")
+        }
+    }
+
+    impl DomId for Id {}
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Model {
+        v: u32,
+        model: String,
+    }
+
+    struct TestPage {
+        version: Option<u32>,
+    }
+
+    impl Page<Model, (), String> for TestPage {
+        type Error = Error;
+
+        fn id(&self) -> &'static dyn DomId {
+            &Id
+        }
+
+        fn init(&self) -> Result<(Model, Effect<()>), Error> {
+            Ok((
+                Model {
+                    v: 0,
+                    model: String::new(),
+                },
+                effect::none(),
+            ))
+        }
+
+        fn subscriptions(&self, _model: &Model) -> Subscription<()> {
+            subscription::none()
+        }
+
+        fn update(&self, _msg: &(), _model: &mut Model) -> Result<Effect<()>, Error> {
+            Ok(effect::none())
+        }
+
+        fn view(&self, _model: &Model) -> PageMarkup<String> {
+            PageMarkup {
+                head: String::new(),
+                body: String::new(),
+            }
+        }
+
+        fn model_migration(&self) -> Option<&dyn ModelMigration<Model>> {
+            self.version.map(|_| self as &dyn ModelMigration<Model>)
+        }
+    }
+
+    impl ModelMigration<Model> for TestPage {
+        fn version(&self) -> u32 {
+            self.version.unwrap_or_default()
+        }
+
+        fn migrate(&self, from_version: u32, model: serde_json::Value) -> Result<Model, Error> {
+            Ok(Model {
+                v: from_version,
+                model: format!("migrated {}", model),
+            })
+        }
+    }
+
+    fn model() -> Model {
+        Model {
+            v: 7,
+            model: "looks like an envelope".to_string(),
+        }
+    }
+
+    #[test]
+    fn unversioned_pages_use_the_bare_model() {
+        let page = TestPage { version: None };
+        let value = encode_model(&page, &model()).unwrap();
+
+        assert_eq!(value, json!({ "v": 7, "model": "looks like an envelope" }));
+        assert_eq!(decode_model(&page, value).unwrap(), model());
+    }
+
+    #[test]
+    fn versioned_pages_always_write_the_envelope() {
+        let page = TestPage { version: Some(2) };
+        let value = encode_model(&page, &model()).unwrap();
+
+        assert_eq!(
+            value,
+            json!({ "v": 2, "model": { "v": 7, "model": "looks like an envelope" } })
+        );
+        assert_eq!(decode_model(&page, value).unwrap(), model());
+    }
+
+    #[test]
+    fn older_versions_are_migrated() {
+        let page = TestPage { version: Some(2) };
+        let value = json!({ "v": 1, "model": "old" });
+
+        assert_eq!(
+            decode_model(&page, value).unwrap(),
+            Model {
+                v: 1,
+                model: "migrated \"old\"".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn bare_models_are_migrated_from_version_zero() {
+        let page = TestPage { version: Some(2) };
+        let value = json!({ "count": 1 });
+
+        assert_eq!(decode_model(&page, value).unwrap().v, 0);
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let page = TestPage { version: Some(2) };
+        let value = json!({ "v": 3, "model": {} });
+
+        assert_eq!(
+            decode_model(&page, value).unwrap_err().code(),
+            "decode_model"
+        );
+    }
+
+    #[test]
+    fn state_script_escapes_script_end_tags() {
+        let state =
+            PageState::from_model(json!({ "s": "</script><script>alert(1)</script>" })).unwrap();
+
+        assert_eq!(
+            state.script(),
+            concat!(
+                r#"<script type="application/json" id="poly-state">"#,
+                r#"{"model":{"s":"\u003c/script>\u003cscript>alert(1)\u003c/script>"},"effects":[]}"#,
+                "</script>"
+            )
+        );
+    }
+
+    #[test]
+    fn state_script_is_inserted_before_the_first_head_end_tag() {
+        let state = PageState::from_model(1).unwrap();
+        let html = "<html><head><title></title></head><body></head></body></html>".to_string();
+
+        assert_eq!(
+            insert_state_script(html, &state),
+            format!(
+                "<html><head><title></title>{}</head><body></head></body></html>",
+                state.script()
+            )
+        );
+    }
+
+    #[test]
+    fn state_script_is_prepended_without_a_head_end_tag() {
+        let state = PageState::from_model(1).unwrap();
+
+        assert_eq!(
+            insert_state_script("<p></p>".to_string(), &state),
+            format!("{}<p></p>", state.script())
+        );
+    }
+
+    #[test]
+    fn view_with_state_embeds_the_model_in_head() {
+        let page = TestPage { version: None };
+        let html = native::view_with_state(&page, &json!({ "v": 7, "model": "</script>" }));
+
+        assert_eq!(
+            html.unwrap(),
+            concat!(
+                r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+                r#"<script type="application/json" id="poly-state">"#,
+                r#"{"model":{"model":"\u003c/script>","v":7},"effects":[]}</script>"#,
+                "</head><body></body></html>"
+            )
+        );
+    }
+
+    #[test]
+    fn init_view_with_state_embeds_the_model_and_effects() {
+        let page = TestPage { version: Some(2) };
+
+        assert_eq!(
+            native::init_view_with_state(&page, true).unwrap(),
+            concat!(
+                r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+                r#"<script type="application/json" id="poly-state">"#,
+                r#"{"model":{"model":{"model":"","v":0},"v":2},"effects":[{"type":"none"}]}"#,
+                "</script></head><body></body></html>"
+            )
+        );
+    }
+
+    fn head_tags() -> Head {
+        Head::new().title("A & B").description("\"quoted\"")
+    }
+
+    #[test]
+    fn render_page_writes_head_tags_inside_head() {
+        let markup = PageMarkup {
+            head: format!("{}<link>", head_tags().to_html()),
+            body: "<p></p>".to_string(),
+        };
+
+        assert_eq!(
+            markup::Markup::render_page(markup),
+            concat!(
+                r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+                r#"<title>A &amp; B</title><meta name="description" content="&quot;quoted&quot;">"#,
+                r#"<link></head><body><p></p></body></html>"#
+            )
+        );
+    }
+
+    #[cfg(feature = "maud")]
+    #[test]
+    fn render_page_maud_writes_head_tags_inside_head() {
+        let markup = PageMarkup {
+            head: html! { (head_tags()) link; },
+            body: html! { p {} },
+        };
+
+        assert_eq!(
+            render_page_maud(markup),
+            concat!(
+                r#"<!DOCTYPE html><html><head><meta charset="utf-8">"#,
+                r#"<title>A &amp; B</title><meta name="description" content="&quot;quoted&quot;">"#,
+                r#"<link></head><body><p></p></body></html>"#
+            )
+        );
+    }
+}
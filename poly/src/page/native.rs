@@ -0,0 +1,247 @@
+use crate::browser::subscription::Subscription;
+use crate::error::Error;
+use crate::markup;
+use crate::page;
+use crate::page::JsMsg;
+use crate::page::ModelAndEffects;
+use crate::page::Page;
+use crate::page::PageState;
+use serde::Serialize;
+
+pub fn init<P, Model, Msg, Markup>(page: &P) -> Result<serde_json::Value, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+    Msg: serde::Serialize,
+{
+    let (model, effect) = page.init().map_err(Into::into)?;
+    encode_model_and_effects(
+        page,
+        ModelAndEffects {
+            model,
+            effects: effect.into_vec(),
+        },
+    )
+}
+
+pub fn view<P, Model, Msg, Markup>(
+    page: &P,
+    json_model: &serde_json::Value,
+) -> Result<String, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+{
+    let model = decode_model(page, json_model)?;
+    Ok(render_page(page, &model))
+}
+
+pub fn view_body<P, Model, Msg, Markup>(
+    page: &P,
+    json_model: &serde_json::Value,
+) -> Result<String, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+{
+    let model = decode_model(page, json_model)?;
+    Ok(render_body(page, &model))
+}
+
+pub fn get_subscriptions<P, Model, Msg, Markup>(
+    page: &P,
+    json_model: &serde_json::Value,
+) -> Result<serde_json::Value, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+    Msg: serde::Serialize,
+{
+    let model = decode_model(page, json_model)?;
+    let subscriptions = page.subscriptions(&model);
+    encode_subscriptions(subscriptions.into_vec())
+}
+
+pub fn update<P, Model, Msg, Markup>(
+    page: &P,
+    json_msg: &serde_json::Value,
+    json_model: &serde_json::Value,
+) -> Result<serde_json::Value, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Msg: serde::Serialize,
+    Msg: serde::de::DeserializeOwned,
+    Model: serde::de::DeserializeOwned,
+    Model: serde::Serialize,
+{
+    let msg = decode_msg(json_msg)?;
+    let mut model = decode_model(page, json_model)?;
+    let effect = page.update(&msg, &mut model).map_err(Into::into)?;
+
+    encode_model_and_effects(
+        page,
+        ModelAndEffects {
+            model,
+            effects: effect.into_vec(),
+        },
+    )
+}
+
+pub fn update_from_js<P, Model, Msg, Markup>(
+    page: &P,
+    json_msg: &serde_json::Value,
+    json_model: &serde_json::Value,
+) -> Result<serde_json::Value, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Msg: serde::Serialize,
+    Model: serde::de::DeserializeOwned,
+    Model: serde::Serialize,
+{
+    let msg: JsMsg = decode_msg(json_msg)?;
+    let mut model = decode_model(page, json_model)?;
+    let effect = page.update_from_js(msg, &mut model).map_err(Into::into)?;
+
+    encode_model_and_effects(
+        page,
+        ModelAndEffects {
+            model,
+            effects: effect.into_vec(),
+        },
+    )
+}
+
+pub fn render_page<P, Model, Msg, Markup>(page: &P, model: &Model) -> String
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+{
+    let markup = page.view(model);
+    page.render_page(markup)
+}
+
+pub fn render_body<P, Model, Msg, Markup>(page: &P, model: &Model) -> String
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+{
+    let markup = page.view(model);
+    page.render(markup.body)
+}
+
+pub fn render_initial_page<P, Model, Msg, Markup>(page: &P) -> Result<String, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+{
+    let (model, _effect) = page.init().map_err(Into::into)?;
+    Ok(render_page(page, &model))
+}
+
+pub fn view_with_state<P, Model, Msg, Markup>(
+    page: &P,
+    json_model: &serde_json::Value,
+) -> Result<String, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+    Model: serde::Serialize,
+{
+    let model = decode_model(page, json_model)?;
+    let state = PageState::from_model(page::encode_model(page, &model)?)?;
+    let markup = page.view(&model);
+
+    Ok(page.render_page_with_state(markup, &state))
+}
+
+pub fn init_view_with_state<P, Model, Msg, Markup>(
+    page: &P,
+    include_effects: bool,
+) -> Result<String, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+    Msg: serde::Serialize,
+{
+    let (model, effect) = page.init().map_err(Into::into)?;
+    let markup = page.view(&model);
+
+    let effects = if include_effects {
+        effect.into_vec()
+    } else {
+        vec![]
+    };
+
+    let state = PageState::new(&ModelAndEffects {
+        model: page::encode_model(page, &model)?,
+        effects,
+    })?;
+
+    Ok(page.render_page_with_state(markup, &state))
+}
+
+fn encode_model_and_effects<P, Model, Msg, Markup>(
+    page: &P,
+    model_and_effects: ModelAndEffects<Model, Msg>,
+) -> Result<serde_json::Value, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+    Msg: serde::Serialize,
+{
+    let model_and_effects = ModelAndEffects {
+        model: page::encode_model(page, &model_and_effects.model)?,
+        effects: model_and_effects.effects,
+    };
+
+    encode_json_value(model_and_effects).map_err(|err| Error::Encode {
+        value: "model and effects".to_string(),
+        message: err.to_string(),
+    })
+}
+
+fn encode_subscriptions<Msg>(
+    subscriptions: Vec<Subscription<Msg>>,
+) -> Result<serde_json::Value, Error>
+where
+    Msg: serde::Serialize,
+{
+    encode_json_value(subscriptions).map_err(|err| Error::Encode {
+        value: "subscriptions".to_string(),
+        message: err.to_string(),
+    })
+}
+
+fn decode_model<P, Model, Msg, Markup>(
+    page: &P,
+    json_model: &serde_json::Value,
+) -> Result<Model, Error>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+{
+    page::decode_model(page, json_model.clone())
+}
+
+fn decode_msg<Msg>(json_msg: &serde_json::Value) -> Result<Msg, Error>
+where
+    Msg: serde::de::DeserializeOwned,
+{
+    Msg::deserialize(json_msg).map_err(|err| Error::DecodeMsg {
+        message: err.to_string(),
+    })
+}
+
+fn encode_json_value(value: impl Serialize) -> Result<serde_json::Value, serde_json::Error> {
+    serde_json::to_value(value)
+}
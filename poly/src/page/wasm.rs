@@ -1,7 +1,15 @@
-use crate::browser::effect::Effect;
+#[cfg(feature = "stateful")]
+pub mod stateful;
+
 use crate::browser::subscription::Subscription;
+use crate::error::Error;
+use crate::markup;
+use crate::page;
+use crate::page::native;
 use crate::page::JsMsg;
+pub use crate::page::ModelAndEffects;
 use crate::page::Page;
+use crate::page::PageState;
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
@@ -11,22 +19,27 @@ const JSON_SERIALIZER: serde_wasm_bindgen::Serializer =
 pub fn init<P, Model, Msg, Markup>(page: &P) -> Result<JsValue, JsValue>
 where
     P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Model: serde::Serialize,
     Msg: serde::Serialize,
 {
-    let (model, effect) = page.init()?;
-    encode_model_and_effects(&ModelAndEffects {
-        model,
-        effects: effect.into_vec(),
-    })
+    let (model, effect) = page.init().map_err(page_error)?;
+    encode_model_and_effects(
+        page,
+        ModelAndEffects {
+            model,
+            effects: effect.into_vec(),
+        },
+    )
 }
 
 pub fn view<P, Model, Msg, Markup>(page: &P, js_model: &JsValue) -> Result<String, JsValue>
 where
     P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Model: serde::de::DeserializeOwned,
 {
-    let model = decode_model(js_model)?;
+    let model = decode_model(page, js_model)?;
     let markup = page.view(&model);
 
     Ok(page.render_page(markup))
@@ -35,24 +48,56 @@ where
 pub fn view_body<P, Model, Msg, Markup>(page: &P, js_model: &JsValue) -> Result<String, JsValue>
 where
     P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Model: serde::de::DeserializeOwned,
 {
-    let model = decode_model(js_model)?;
+    let model = decode_model(page, js_model)?;
     let markup = page.view(&model);
 
     Ok(page.render(markup.body))
 }
 
+pub fn view_with_state<P, Model, Msg, Markup>(
+    page: &P,
+    js_model: &JsValue,
+) -> Result<String, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+    Model: serde::Serialize,
+{
+    let model = decode_model(page, js_model)?;
+    let state = PageState::from_model(page::encode_model(page, &model)?)?;
+    let markup = page.view(&model);
+
+    Ok(page.render_page_with_state(markup, &state))
+}
+
+pub fn init_view_with_state<P, Model, Msg, Markup>(
+    page: &P,
+    include_effects: bool,
+) -> Result<String, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+    Msg: serde::Serialize,
+{
+    Ok(native::init_view_with_state(page, include_effects)?)
+}
+
 pub fn get_subscriptions<P, Model, Msg, Markup>(
     page: &P,
     js_model: &JsValue,
 ) -> Result<JsValue, JsValue>
 where
     P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Model: serde::de::DeserializeOwned,
     Msg: serde::Serialize,
 {
-    let model = decode_model(js_model)?;
+    let model = decode_model(page, js_model)?;
     let subscriptions = page.subscriptions(&model);
     encode_subscriptions(subscriptions.into_vec())
 }
@@ -64,19 +109,23 @@ pub fn update<P, Model, Msg, Markup>(
 ) -> Result<JsValue, JsValue>
 where
     P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Msg: serde::Serialize,
     Msg: serde::de::DeserializeOwned,
     Model: serde::de::DeserializeOwned,
     Model: serde::Serialize,
 {
     let msg = decode_msg(js_msg)?;
-    let mut model = decode_model(js_model)?;
-    let effect = page.update(&msg, &mut model)?;
-
-    encode_model_and_effects(&ModelAndEffects {
-        model,
-        effects: effect.into_vec(),
-    })
+    let mut model = decode_model(page, js_model)?;
+    let effect = page.update(&msg, &mut model).map_err(page_error)?;
+
+    encode_model_and_effects(
+        page,
+        ModelAndEffects {
+            model,
+            effects: effect.into_vec(),
+        },
+    )
 }
 
 pub fn update_from_js<P, Model, Msg, Markup>(
@@ -86,19 +135,23 @@ pub fn update_from_js<P, Model, Msg, Markup>(
 ) -> Result<JsValue, JsValue>
 where
     P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Msg: serde::Serialize,
     Msg: serde::de::DeserializeOwned,
     Model: serde::de::DeserializeOwned,
     Model: serde::Serialize,
 {
     let msg = decode_value(js_msg)?;
-    let mut model = decode_model(js_model)?;
-    let effect = page.update_from_js(msg, &mut model)?;
-
-    encode_model_and_effects(&ModelAndEffects {
-        model,
-        effects: effect.into_vec(),
-    })
+    let mut model = decode_model(page, js_model)?;
+    let effect = page.update_from_js(msg, &mut model).map_err(page_error)?;
+
+    encode_model_and_effects(
+        page,
+        ModelAndEffects {
+            model,
+            effects: effect.into_vec(),
+        },
+    )
 }
 
 pub fn encode_js_value(value: impl Serialize) -> Result<JsValue, serde_wasm_bindgen::Error> {
@@ -112,47 +165,87 @@ where
     serde_wasm_bindgen::from_value(js_value.clone())
 }
 
-fn encode_model_and_effects<Model, Msg>(
-    model_and_effects: &ModelAndEffects<Model, Msg>,
+fn encode_model_and_effects<P, Model, Msg, Markup>(
+    page: &P,
+    model_and_effects: ModelAndEffects<Model, Msg>,
 ) -> Result<JsValue, JsValue>
 where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Model: serde::Serialize,
     Msg: serde::Serialize,
 {
-    encode_js_value(model_and_effects)
-        .map_err(|err| format!("Failed to encode model and effects: {}", err).into())
+    let model_and_effects = ModelAndEffects {
+        model: page::encode_model(page, &model_and_effects.model)?,
+        effects: model_and_effects.effects,
+    };
+
+    encode_js_value(model_and_effects).map_err(|err| {
+        Error::Encode {
+            value: "model and effects".to_string(),
+            message: err.to_string(),
+        }
+        .into()
+    })
 }
 
 fn encode_subscriptions<Msg>(subscriptions: Vec<Subscription<Msg>>) -> Result<JsValue, JsValue>
 where
     Msg: serde::Serialize,
 {
-    encode_js_value(subscriptions)
-        .map_err(|err| format!("Failed to encode subscriptions: {}", err).into())
+    encode_js_value(subscriptions).map_err(|err| {
+        Error::Encode {
+            value: "subscriptions".to_string(),
+            message: err.to_string(),
+        }
+        .into()
+    })
 }
 
-fn decode_model<Model>(js_model: &JsValue) -> Result<Model, JsValue>
+fn decode_model<P, Model, Msg, Markup>(page: &P, js_model: &JsValue) -> Result<Model, JsValue>
 where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
     Model: serde::de::DeserializeOwned,
 {
-    decode_js_value(js_model.clone())
-        .map_err(|err| format!("Failed to decode model: {}", err).into())
+    let value = decode_js_value(js_model.clone()).map_err(|err| Error::DecodeModel {
+        message: err.to_string(),
+    })?;
+
+    Ok(page::decode_model(page, value)?)
 }
 
 fn decode_msg<Msg>(js_msg: &JsValue) -> Result<Msg, JsValue>
 where
     Msg: serde::de::DeserializeOwned,
 {
-    decode_js_value(js_msg.clone()).map_err(|err| format!("Failed to decode msg: {}", err).into())
+    decode_js_value(js_msg.clone()).map_err(|err| {
+        Error::DecodeMsg {
+            message: err.to_string(),
+        }
+        .into()
+    })
 }
 
 fn decode_value(js_msg: &JsValue) -> Result<JsMsg, JsValue> {
-    decode_js_value(js_msg.clone()).map_err(|err| format!("Failed to decode msg: {}", err).into())
+    decode_js_value(js_msg.clone()).map_err(|err| {
+        Error::DecodeMsg {
+            message: err.to_string(),
+        }
+        .into()
+    })
+}
+
+fn page_error<E>(err: E) -> JsValue
+where
+    E: Into<Error>,
+{
+    let err: Error = err.into();
+    err.into()
 }
 
-#[derive(Clone, serde::Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ModelAndEffects<Model, Msg> {
-    pub model: Model,
-    pub effects: Vec<Effect<Msg>>,
+impl From<Error> for JsValue {
+    fn from(err: Error) -> Self {
+        encode_js_value(&err).unwrap_or_else(|_| JsValue::from_str(&err.to_string()))
+    }
 }
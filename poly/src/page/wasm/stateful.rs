@@ -0,0 +1,244 @@
+use crate::browser::effect::Effect;
+use crate::error::Error;
+use crate::markup;
+use crate::page;
+use crate::page::wasm::decode_model;
+use crate::page::wasm::decode_msg;
+use crate::page::wasm::decode_value;
+use crate::page::wasm::encode_js_value;
+use crate::page::wasm::encode_subscriptions;
+use crate::page::wasm::page_error;
+use crate::page::Page;
+use crate::page::PageState;
+use crate::vdom::VirtualDom;
+use std::cell::RefCell;
+use wasm_bindgen::prelude::*;
+
+pub struct State<Model> {
+    model: RefCell<Option<Model>>,
+    dom: RefCell<VirtualDom>,
+}
+
+impl<Model> State<Model> {
+    pub fn new() -> State<Model> {
+        State {
+            model: RefCell::new(None),
+            dom: RefCell::new(VirtualDom::new()),
+        }
+    }
+
+    fn with_model<T>(
+        &self,
+        f: impl FnOnce(&mut Model) -> Result<T, JsValue>,
+    ) -> Result<T, JsValue> {
+        let mut model = self.model.borrow_mut();
+        let model = model.as_mut().ok_or(Error::ModelNotInitialized)?;
+        f(model)
+    }
+
+    // Updates run on a copy, so a failed update leaves the stored model untouched
+    fn update_model<T>(
+        &self,
+        f: impl FnOnce(&mut Model) -> Result<T, JsValue>,
+    ) -> Result<T, JsValue>
+    where
+        Model: Clone,
+    {
+        let mut model = self
+            .model
+            .borrow()
+            .clone()
+            .ok_or(Error::ModelNotInitialized)?;
+
+        let result = f(&mut model)?;
+        self.model.replace(Some(model));
+        Ok(result)
+    }
+
+    fn set_model(&self, model: Model) {
+        self.model.replace(Some(model));
+        self.dom.borrow_mut().reset();
+    }
+}
+
+impl<Model> Default for State<Model> {
+    fn default() -> Self {
+        State::new()
+    }
+}
+
+pub fn init<P, Model, Msg, Markup>(page: &P, state: &State<Model>) -> Result<JsValue, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Msg: serde::Serialize,
+{
+    let (model, effect) = page.init().map_err(page_error)?;
+    state.set_model(model);
+    encode_effects(effect)
+}
+
+pub fn view<P, Model, Msg, Markup>(page: &P, state: &State<Model>) -> Result<String, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+{
+    state.with_model(|model| {
+        let markup = page.view(model);
+        state.dom.borrow_mut().reset();
+        Ok(page.render_page(markup))
+    })
+}
+
+pub fn view_body<P, Model, Msg, Markup>(page: &P, state: &State<Model>) -> Result<String, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+{
+    state.with_model(|model| {
+        let markup = page.view(model);
+        let html = page.render(markup.body);
+        state.dom.borrow_mut().set_html(&html);
+        Ok(html)
+    })
+}
+
+pub fn view_body_patches<P, Model, Msg, Markup>(
+    page: &P,
+    state: &State<Model>,
+) -> Result<JsValue, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+{
+    state.with_model(|model| {
+        let markup = page.view(model);
+        let html = page.render(markup.body);
+        let patches = state.dom.borrow_mut().patch(&html);
+
+        encode_js_value(patches).map_err(|err| {
+            Error::Encode {
+                value: "patches".to_string(),
+                message: err.to_string(),
+            }
+            .into()
+        })
+    })
+}
+
+pub fn view_with_state<P, Model, Msg, Markup>(
+    page: &P,
+    state: &State<Model>,
+) -> Result<String, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+{
+    state.with_model(|model| {
+        let page_state = PageState::from_model(page::encode_model(page, model)?)?;
+        let markup = page.view(model);
+        state.dom.borrow_mut().reset();
+        Ok(page.render_page_with_state(markup, &page_state))
+    })
+}
+
+pub fn get_subscriptions<P, Model, Msg, Markup>(
+    page: &P,
+    state: &State<Model>,
+) -> Result<JsValue, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Msg: serde::Serialize,
+{
+    state.with_model(|model| {
+        let subscriptions = page.subscriptions(model);
+        encode_subscriptions(subscriptions.into_vec())
+    })
+}
+
+pub fn update<P, Model, Msg, Markup>(
+    page: &P,
+    state: &State<Model>,
+    js_msg: &JsValue,
+) -> Result<JsValue, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: Clone,
+    Msg: serde::Serialize,
+    Msg: serde::de::DeserializeOwned,
+{
+    let msg = decode_msg(js_msg)?;
+
+    state.update_model(|model| {
+        let effect = page.update(&msg, model).map_err(page_error)?;
+        encode_effects(effect)
+    })
+}
+
+pub fn update_from_js<P, Model, Msg, Markup>(
+    page: &P,
+    state: &State<Model>,
+    js_msg: &JsValue,
+) -> Result<JsValue, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: Clone,
+    Msg: serde::Serialize,
+{
+    let msg = decode_value(js_msg)?;
+
+    state.update_model(|model| {
+        let effect = page.update_from_js(msg, model).map_err(page_error)?;
+        encode_effects(effect)
+    })
+}
+
+pub fn get_model<P, Model, Msg, Markup>(page: &P, state: &State<Model>) -> Result<JsValue, JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::Serialize,
+{
+    state.with_model(|model| {
+        let value = page::encode_model(page, model)?;
+        encode_js_value(value).map_err(|err| {
+            Error::Encode {
+                value: "model".to_string(),
+                message: err.to_string(),
+            }
+            .into()
+        })
+    })
+}
+
+pub fn set_model<P, Model, Msg, Markup>(
+    page: &P,
+    state: &State<Model>,
+    js_model: &JsValue,
+) -> Result<(), JsValue>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Model: serde::de::DeserializeOwned,
+{
+    let model = decode_model(page, js_model)?;
+    state.set_model(model);
+    Ok(())
+}
+
+fn encode_effects<Msg>(effect: Effect<Msg>) -> Result<JsValue, JsValue>
+where
+    Msg: serde::Serialize,
+{
+    encode_js_value(effect.into_vec()).map_err(|err| {
+        Error::Encode {
+            value: "effects".to_string(),
+            message: err.to_string(),
+        }
+        .into()
+    })
+}
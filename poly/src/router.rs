@@ -0,0 +1,379 @@
+use crate::browser::effect::effectful_msg::effectful_msg;
+use crate::browser::effect::navigation;
+use crate::browser::effect::Effect;
+use crate::browser::subscription;
+use crate::browser::subscription::event_listener;
+use crate::browser::subscription::Subscription;
+use crate::browser::value::Capture;
+use crate::route::Route;
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::str::FromStr;
+
+pub trait ParseRoute: Route + Sized {
+    fn from_url(url: &Url) -> Option<Self>;
+
+    fn from_path(path: &str) -> Option<Self> {
+        Self::from_url(&Url::parse(path))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Url {
+    pub segments: Vec<String>,
+    pub query: Vec<(String, String)>,
+    pub fragment: Option<String>,
+}
+
+impl Url {
+    pub fn parse(s: &str) -> Url {
+        let path = strip_origin(s);
+
+        let (path, fragment) = match path.split_once('#') {
+            Some((path, fragment)) => (path, Some(decode(fragment))),
+            None => (path, None),
+        };
+
+        let (path, query) = match path.split_once('?') {
+            Some((path, query)) => (path, parse_query(query)),
+            None => (path, vec![]),
+        };
+
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(decode)
+            .collect();
+
+        Url {
+            segments,
+            query,
+            fragment,
+        }
+    }
+
+    pub fn from_segments(segments: &[&str]) -> Url {
+        Url {
+            segments: segments.iter().map(|s| s.to_string()).collect(),
+            query: vec![],
+            fragment: None,
+        }
+    }
+
+    pub fn with_query(mut self, name: &str, value: impl fmt::Display) -> Url {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_optional_query<T>(self, name: &str, value: Option<T>) -> Url
+    where
+        T: fmt::Display,
+    {
+        match value {
+            Some(value) => self.with_query(name, value),
+            None => self,
+        }
+    }
+
+    pub fn with_fragment(mut self, fragment: &str) -> Url {
+        self.fragment = Some(fragment.to_string());
+        self
+    }
+
+    pub fn path(&self) -> String {
+        format!(
+            "/{}",
+            self.segments
+                .iter()
+                .map(|segment| encode(segment))
+                .collect::<Vec<_>>()
+                .join("/")
+        )
+    }
+
+    pub fn query_value(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn query_param<T>(&self, name: &str) -> Option<T>
+    where
+        T: FromStr,
+    {
+        self.query_value(name).and_then(|value| value.parse().ok())
+    }
+
+    pub fn has_query(&self, name: &str) -> bool {
+        self.query.iter().any(|(key, _)| key == name)
+    }
+
+    pub fn parser(&self) -> PathParser<'_> {
+        PathParser {
+            url: self,
+            position: 0,
+        }
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path())?;
+
+        if !self.query.is_empty() {
+            let query: Vec<String> = self
+                .query
+                .iter()
+                .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+                .collect();
+
+            write!(f, "?{}", query.join("&"))?;
+        }
+
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", encode(fragment))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for Url {
+    fn deserialize<D>(deserializer: D) -> Result<Url, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Url::parse(&s))
+    }
+}
+
+pub struct PathParser<'a> {
+    url: &'a Url,
+    position: usize,
+}
+
+impl<'a> PathParser<'a> {
+    pub fn exact(&mut self, segment: &str) -> Option<()> {
+        let current = self.url.segments.get(self.position)?;
+
+        if current == segment {
+            self.position += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    pub fn param<T>(&mut self) -> Option<T>
+    where
+        T: FromStr,
+    {
+        let value = self.url.segments.get(self.position)?.parse().ok()?;
+        self.position += 1;
+        Some(value)
+    }
+
+    pub fn optional_param<T>(&mut self) -> Option<Option<T>>
+    where
+        T: FromStr,
+    {
+        match self.url.segments.get(self.position) {
+            Some(_) => self.param().map(Some),
+            None => Some(None),
+        }
+    }
+
+    pub fn rest(&mut self) -> Vec<String> {
+        let rest = self.url.segments[self.position..].to_vec();
+        self.position = self.url.segments.len();
+        rest
+    }
+
+    pub fn end(&self) -> Option<()> {
+        if self.position == self.url.segments.len() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    pub fn query<T>(&self, name: &str) -> Option<T>
+    where
+        T: FromStr,
+    {
+        self.url.query_param(name)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RouteChange<R> {
+    pub url: Url,
+    pub route: Option<R>,
+}
+
+impl<R> Default for RouteChange<R> {
+    fn default() -> Self {
+        RouteChange {
+            url: Url::default(),
+            route: None,
+        }
+    }
+}
+
+impl<'de, R> Deserialize<'de> for RouteChange<R>
+where
+    R: ParseRoute,
+{
+    fn deserialize<D>(deserializer: D) -> Result<RouteChange<R>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let url = Url::deserialize(deserializer)?;
+        let route = R::from_url(&url);
+        Ok(RouteChange { url, route })
+    }
+}
+
+pub fn on_route_change<Msg, ToMsg, R>(to_msg: ToMsg) -> Subscription<Msg>
+where
+    ToMsg: Fn(Capture<RouteChange<R>>) -> Msg,
+    R: ParseRoute,
+{
+    subscription::batch(vec![
+        event_listener::on_popstate(&to_msg),
+        event_listener::on_internal_link_click(&to_msg),
+    ])
+}
+
+pub fn push_route<Msg, R>(route: &R) -> Effect<Msg>
+where
+    R: Route,
+{
+    navigation::push_url(&route.path())
+}
+
+pub fn replace_route<Msg, R>(route: &R) -> Effect<Msg>
+where
+    R: Route,
+{
+    navigation::replace_url(&route.path())
+}
+
+pub fn current_route<Msg, ToMsg, R>(to_msg: ToMsg) -> Effect<Msg>
+where
+    ToMsg: Fn(Capture<RouteChange<R>>) -> Msg,
+    R: ParseRoute,
+{
+    let msg = to_msg(Default::default());
+    effectful_msg(msg, navigation::get_current_url())
+}
+
+fn strip_origin(s: &str) -> &str {
+    let path_start = s.find(['/', '?', '#']).unwrap_or(s.len());
+
+    match s.find("://") {
+        Some(index) if index < path_start => {
+            let rest = &s[index + 3..];
+            match rest.find(['/', '?', '#']) {
+                Some(index) => &rest[index..],
+                None => "",
+            }
+        }
+
+        _ => s,
+    }
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode_query(key), decode_query(value)),
+            None => (decode_query(pair), String::new()),
+        })
+        .collect()
+}
+
+fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some([b'%', high, low]) => hex_value(*high).zip(hex_value(*low)),
+            _ => None,
+        };
+
+        match escaped {
+            Some((high, low)) => {
+                decoded.push(high << 4 | low);
+                i += 3;
+            }
+
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn decode_query(s: &str) -> String {
+    decode(&s.replace('+', " "))
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_strips_origin() {
+        let url = Url::parse("https://example.com/posts/1?page=2#top");
+
+        assert_eq!(url.segments, vec!["posts", "1"]);
+        assert_eq!(url.query_value("page"), Some("2"));
+        assert_eq!(url.fragment, Some("top".to_string()));
+    }
+
+    #[test]
+    fn parse_keeps_urls_inside_query() {
+        let url = Url::parse("/login?next=https://x.y/z");
+
+        assert_eq!(url.segments, vec!["login"]);
+        assert_eq!(url.query_value("next"), Some("https://x.y/z"));
+    }
+
+    #[test]
+    fn parse_keeps_urls_inside_fragment() {
+        let url = Url::parse("/docs#https://x.y/z");
+
+        assert_eq!(url.segments, vec!["docs"]);
+        assert_eq!(url.fragment, Some("https://x.y/z".to_string()));
+    }
+}
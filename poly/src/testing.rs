@@ -0,0 +1,600 @@
+use crate::browser::dom_id::DomId;
+use crate::browser::effect::clipboard::Clipboard;
+use crate::browser::effect::dom::Dom;
+use crate::browser::effect::effectful_msg::EffectfulMsg;
+use crate::browser::effect::http::Http;
+use crate::browser::effect::http::Method;
+use crate::browser::effect::local_storage::LocalStorage;
+use crate::browser::effect::navigation::Navigation;
+use crate::browser::effect::session_storage::SessionStorage;
+use crate::browser::effect::time::Time;
+use crate::browser::effect::Effect;
+use crate::browser::selector::Selector;
+use crate::browser::subscription::event_listener::EventListener;
+use crate::browser::subscription::event_listener::EventMatcher;
+use crate::browser::subscription::event_listener::EventType;
+use crate::browser::subscription::event_listener::ListenTarget;
+use crate::browser::subscription::Subscription;
+use crate::browser::subscription::SubscriptionMsg;
+use crate::browser::value::CAPTURE_VALUE;
+use crate::browser::WindowSize;
+use crate::error::Error;
+use crate::markup;
+use crate::page::Page;
+use crate::page::PageMarkup;
+use crate::time::Posix;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+const MAX_MESSAGES_PER_SEND: usize = 1000;
+
+#[derive(Clone, Default)]
+pub struct FakeBrowser {
+    pub local_storage: BTreeMap<String, serde_json::Value>,
+    pub session_storage: BTreeMap<String, serde_json::Value>,
+    pub element_values: BTreeMap<String, String>,
+    pub http_responses: BTreeMap<(Method, String), serde_json::Value>,
+    pub clipboard: Option<String>,
+    pub current_time: Posix,
+    pub current_url: String,
+    pub window_size: WindowSize,
+    pub title: Option<String>,
+}
+
+impl FakeBrowser {
+    pub fn new() -> FakeBrowser {
+        FakeBrowser {
+            current_url: "/".to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn local_storage_item<V>(mut self, key: &str, value: V) -> FakeBrowser
+    where
+        V: serde::Serialize,
+    {
+        self.local_storage.insert(key.to_string(), json!(value));
+        self
+    }
+
+    pub fn session_storage_item<V>(mut self, key: &str, value: V) -> FakeBrowser
+    where
+        V: serde::Serialize,
+    {
+        self.session_storage.insert(key.to_string(), json!(value));
+        self
+    }
+
+    pub fn element_value(mut self, id: impl DomId, value: &str) -> FakeBrowser {
+        self.element_values
+            .insert(id.to_string(), value.to_string());
+        self
+    }
+
+    pub fn http_response<V>(
+        mut self,
+        method: Method,
+        url: &str,
+        status: u16,
+        body: V,
+    ) -> FakeBrowser
+    where
+        V: serde::Serialize,
+    {
+        let response = if (200..300).contains(&status) {
+            json!({
+                "type": "ok",
+                "config": { "status": status, "headers": {}, "body": body },
+            })
+        } else {
+            let body = json!(body).to_string();
+
+            json!({
+                "type": "err",
+                "config": {
+                    "type": "badStatus",
+                    "config": { "status": status, "headers": {}, "body": body },
+                },
+            })
+        };
+
+        self.http_responses
+            .insert((method, url.to_string()), response);
+        self
+    }
+
+    pub fn current_time(mut self, time: Posix) -> FakeBrowser {
+        self.current_time = time;
+        self
+    }
+
+    pub fn current_url(mut self, url: &str) -> FakeBrowser {
+        self.current_url = url.to_string();
+        self
+    }
+
+    pub fn window_size(mut self, width: u32, height: u32) -> FakeBrowser {
+        self.window_size = WindowSize { width, height };
+        self
+    }
+
+    fn apply<Msg>(&mut self, effect: &Effect<Msg>) {
+        match effect {
+            Effect::Navigation(Navigation::PushUrl(url) | Navigation::ReplaceUrl(url)) => {
+                self.current_url = url.clone();
+            }
+
+            Effect::Dom(Dom::SetTitle { title }) => {
+                self.title = Some(title.clone());
+            }
+
+            _ => {}
+        }
+    }
+
+    fn resolve<Msg>(&mut self, effect: &Effect<Msg>) -> Option<serde_json::Value> {
+        match effect {
+            Effect::LocalStorage(LocalStorage::GetItem { key }) => Some(
+                self.local_storage
+                    .get(key)
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null),
+            ),
+
+            Effect::LocalStorage(LocalStorage::SetItem { key, value }) => {
+                self.local_storage.insert(key.clone(), json!(value));
+                Some(json!(true))
+            }
+
+            Effect::SessionStorage(SessionStorage::GetItem { key }) => Some(
+                self.session_storage
+                    .get(key)
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null),
+            ),
+
+            Effect::SessionStorage(SessionStorage::SetItem { key, value }) => {
+                self.session_storage.insert(key.clone(), json!(value));
+                Some(json!(true))
+            }
+
+            Effect::Time(Time::CurrentTime) => Some(json!(self.current_time)),
+
+            Effect::Dom(Dom::GetElementValue {
+                element_id,
+                parse_as_json,
+            }) => {
+                let value = self.element_values.get(element_id)?;
+
+                if *parse_as_json {
+                    serde_json::from_str(value).ok()
+                } else {
+                    Some(json!(value))
+                }
+            }
+
+            Effect::Dom(Dom::GetWindowSize) => Some(json!(self.window_size)),
+
+            Effect::Clipboard(Clipboard::WriteText { text }) => {
+                self.clipboard = Some(text.clone());
+                Some(json!({ "success": true, "error": null }))
+            }
+
+            Effect::Http(Http::SendRequest(request)) => Some(
+                self.http_responses
+                    .get(&(request.method, request.url.clone()))
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        let message = format!(
+                            "No fake response for {} {}",
+                            json!(request.method).as_str().unwrap_or_default(),
+                            request.url
+                        );
+
+                        json!({
+                            "type": "err",
+                            "config": {
+                                "type": "network",
+                                "config": { "message": message },
+                            },
+                        })
+                    }),
+            ),
+
+            Effect::Navigation(Navigation::GetCurrentUrl | Navigation::FollowLink) => {
+                Some(json!(self.current_url))
+            }
+
+            Effect::Browser(_) => Some(serde_json::Value::Null),
+
+            _ => None,
+        }
+    }
+}
+
+pub struct PageSimulator<P, Model, Msg, Markup> {
+    page: P,
+    model: Model,
+    browser: FakeBrowser,
+    effects: Vec<Effect<Msg>>,
+    pending: VecDeque<EffectfulMsg<Msg>>,
+    markup: PhantomData<Markup>,
+}
+
+impl<P, Model, Msg, Markup> PageSimulator<P, Model, Msg, Markup>
+where
+    P: Page<Model, Msg, Markup>,
+    Markup: markup::Markup,
+    Msg: Clone + serde::Serialize + serde::de::DeserializeOwned,
+{
+    pub fn new(page: P) -> Result<Self, Error> {
+        Self::with_browser(page, FakeBrowser::new())
+    }
+
+    pub fn with_browser(page: P, browser: FakeBrowser) -> Result<Self, Error> {
+        let (model, effect) = page.init().map_err(Into::into)?;
+
+        let mut simulator = PageSimulator {
+            page,
+            model,
+            browser,
+            effects: vec![],
+            pending: VecDeque::new(),
+            markup: PhantomData,
+        };
+
+        let mut queue = VecDeque::new();
+        simulator.run_effect(effect, &mut queue)?;
+        simulator.run(queue)?;
+        Ok(simulator)
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    pub fn model_mut(&mut self) -> &mut Model {
+        &mut self.model
+    }
+
+    pub fn browser(&self) -> &FakeBrowser {
+        &self.browser
+    }
+
+    pub fn browser_mut(&mut self) -> &mut FakeBrowser {
+        &mut self.browser
+    }
+
+    pub fn effects(&self) -> &[Effect<Msg>] {
+        &self.effects
+    }
+
+    pub fn take_effects(&mut self) -> Vec<Effect<Msg>> {
+        std::mem::take(&mut self.effects)
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &EffectfulMsg<Msg>> {
+        self.pending.iter()
+    }
+
+    pub fn resolve_pending<V>(&mut self, value: V) -> Result<(), Error>
+    where
+        V: serde::Serialize,
+    {
+        let pending = self
+            .pending
+            .pop_front()
+            .ok_or_else(|| simulator_error("No pending effects to resolve".to_string()))?;
+
+        let msg = fill_capture(&pending.msg, json!(value))?;
+        self.send(msg)
+    }
+
+    pub fn send(&mut self, msg: Msg) -> Result<(), Error> {
+        self.run(VecDeque::from([msg]))
+    }
+
+    pub fn view(&self) -> PageMarkup<Markup> {
+        self.page.view(&self.model)
+    }
+
+    pub fn subscriptions(&self) -> Vec<Subscription<Msg>> {
+        self.page.subscriptions(&self.model).into_vec()
+    }
+
+    pub fn click(&mut self, id: impl DomId) -> Result<(), Error> {
+        self.fire_event(EventType::Click, id)
+    }
+
+    pub fn input(&mut self, id: impl DomId, value: &str) -> Result<(), Error> {
+        self.browser
+            .element_values
+            .insert(id.to_string(), value.to_string());
+
+        self.fire_event(EventType::Input, id)
+    }
+
+    pub fn fire_event(&mut self, event_type: EventType, id: impl DomId) -> Result<(), Error> {
+        let selector = id.selector();
+
+        let msg = self
+            .subscriptions()
+            .into_iter()
+            .find_map(|subscription| match subscription {
+                Subscription::EventListener(listener)
+                    if listener.event_type == event_type && listens_to(&listener, &selector) =>
+                {
+                    Some(listener.msg)
+                }
+
+                _ => None,
+            })
+            .ok_or_else(|| {
+                simulator_error(format!("No listener for {} on {}", json!(event_type), id))
+            })?;
+
+        self.run_subscription_msg(msg)
+    }
+
+    pub fn fire_interval(&mut self, duration: Duration) -> Result<(), Error> {
+        let duration = duration.as_millis() as u64;
+
+        let msg = self
+            .subscriptions()
+            .into_iter()
+            .find_map(|subscription| match subscription {
+                Subscription::Interval(interval) if interval.duration == duration => {
+                    Some(interval.msg)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| simulator_error(format!("No interval with duration {}ms", duration)))?;
+
+        self.run_subscription_msg(msg)
+    }
+
+    fn run_subscription_msg(&mut self, msg: SubscriptionMsg<Msg>) -> Result<(), Error> {
+        match msg {
+            SubscriptionMsg::Pure(msg) => self.send(msg),
+            SubscriptionMsg::Effectful { msg, effect } => {
+                let mut queue = VecDeque::new();
+                self.run_effectful_msg(EffectfulMsg { msg, effect }, &mut queue)?;
+                self.run(queue)
+            }
+        }
+    }
+
+    // Messages produced by effects are queued instead of sent recursively,
+    // so a message that keeps triggering itself fails instead of overflowing the stack
+    fn run(&mut self, mut queue: VecDeque<Msg>) -> Result<(), Error> {
+        let mut processed = 0;
+
+        while let Some(msg) = queue.pop_front() {
+            processed += 1;
+
+            if processed > MAX_MESSAGES_PER_SEND {
+                return Err(simulator_error(format!(
+                    "More than {} messages were triggered by a single message",
+                    MAX_MESSAGES_PER_SEND
+                )));
+            }
+
+            let effect = self
+                .page
+                .update(&msg, &mut self.model)
+                .map_err(Into::into)?;
+            self.run_effect(effect, &mut queue)?;
+        }
+
+        Ok(())
+    }
+
+    fn run_effect(&mut self, effect: Effect<Msg>, queue: &mut VecDeque<Msg>) -> Result<(), Error> {
+        for effect in effect.into_vec() {
+            match effect {
+                Effect::None => {}
+
+                Effect::EffectfulMsg(effectful_msg) => {
+                    self.effects
+                        .push(Effect::EffectfulMsg(effectful_msg.clone()));
+                    self.run_effectful_msg(*effectful_msg, queue)?;
+                }
+
+                _ => {
+                    self.browser.apply(&effect);
+                    self.effects.push(effect);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn run_effectful_msg(
+        &mut self,
+        effectful_msg: EffectfulMsg<Msg>,
+        queue: &mut VecDeque<Msg>,
+    ) -> Result<(), Error> {
+        match self.browser.resolve(&effectful_msg.effect) {
+            Some(value) => {
+                queue.push_back(fill_capture(&effectful_msg.msg, value)?);
+                Ok(())
+            }
+
+            None => {
+                self.pending.push_back(effectful_msg);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn listens_to<Msg>(listener: &EventListener<Msg>, selector: &Selector) -> bool {
+    let matches_selector = |matcher: &EventMatcher| match matcher {
+        EventMatcher::ExactSelector { selector: s }
+        | EventMatcher::ClosestSelector { selector: s } => s == selector,
+        _ => false,
+    };
+
+    match &listener.listen_target {
+        ListenTarget::Element { selector: s } => s == selector,
+        _ => listener.matchers.iter().any(matches_selector),
+    }
+}
+
+fn simulator_error(message: String) -> Error {
+    Error::user("simulator", &message)
+}
+
+fn fill_capture<Msg>(msg: &Msg, value: serde_json::Value) -> Result<Msg, Error>
+where
+    Msg: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let mut json_msg = serde_json::to_value(msg).map_err(|err| Error::Encode {
+        value: "msg".to_string(),
+        message: err.to_string(),
+    })?;
+
+    replace_capture(&mut json_msg, &value);
+
+    serde_json::from_value(json_msg).map_err(|err| Error::DecodeMsg {
+        message: err.to_string(),
+    })
+}
+
+fn replace_capture(json: &mut serde_json::Value, value: &serde_json::Value) {
+    match json {
+        serde_json::Value::String(s) if s == CAPTURE_VALUE => *json = value.clone(),
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .for_each(|json| replace_capture(json, value)),
+        serde_json::Value::Object(map) => map
+            .values_mut()
+            .for_each(|json| replace_capture(json, value)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::effect;
+    use crate::browser::effect::http;
+    use crate::browser::effect::http::HttpResponse;
+    use crate::browser::effect::local_storage;
+    use crate::browser::subscription;
+    use crate::browser::value::Capture;
+    use std::fmt;
+
+    struct Id;
+
+    impl fmt::Display for Id {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "test-page")
+        }
+    }
+
+    impl DomId for Id {}
+
+    #[derive(Default)]
+    struct Model {
+        responses: Vec<String>,
+        loops: usize,
+    }
+
+    #[derive(Clone, serde::Serialize, serde::Deserialize)]
+    enum Msg {
+        Get,
+        Post,
+        GotResponse(Capture<HttpResponse<String>>),
+        Loop(Capture<serde_json::Value>),
+    }
+
+    struct TestPage;
+
+    impl Page<Model, Msg, String> for TestPage {
+        type Error = Error;
+
+        fn id(&self) -> &'static dyn DomId {
+            &Id
+        }
+
+        fn init(&self) -> Result<(Model, Effect<Msg>), Error> {
+            Ok((Model::default(), effect::none()))
+        }
+
+        fn subscriptions(&self, _model: &Model) -> Subscription<Msg> {
+            subscription::none()
+        }
+
+        fn update(&self, msg: &Msg, model: &mut Model) -> Result<Effect<Msg>, Error> {
+            match msg {
+                Msg::Get => Ok(http::get("/items", Msg::GotResponse)),
+                Msg::Post => Ok(http::post_json("/items", "new", Msg::GotResponse)),
+                Msg::GotResponse(response) => {
+                    let body = match response.value_ref() {
+                        HttpResponse::Ok(response) => response.body.clone(),
+                        HttpResponse::Err(err) => err.to_string(),
+                    };
+                    model.responses.push(body);
+                    Ok(effect::none())
+                }
+                Msg::Loop(_) => {
+                    model.loops += 1;
+                    Ok(local_storage::get_item("key", Msg::Loop))
+                }
+            }
+        }
+
+        fn view(&self, _model: &Model) -> PageMarkup<String> {
+            PageMarkup {
+                head: String::new(),
+                body: String::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn http_responses_are_keyed_on_method_and_url() {
+        let browser = FakeBrowser::new()
+            .http_response(Method::Get, "/items", 200, "listed")
+            .http_response(Method::Post, "/items", 201, "created");
+
+        let mut simulator = PageSimulator::with_browser(TestPage, browser).unwrap();
+        simulator.send(Msg::Get).unwrap();
+        simulator.send(Msg::Post).unwrap();
+
+        assert_eq!(simulator.model().responses, vec!["listed", "created"]);
+    }
+
+    #[test]
+    fn missing_http_response_names_method() {
+        let mut simulator = PageSimulator::new(TestPage).unwrap();
+        simulator.send(Msg::Post).unwrap();
+
+        assert_eq!(
+            simulator.model().responses,
+            vec!["Network error: No fake response for POST /items"]
+        );
+    }
+
+    #[test]
+    fn self_triggering_msg_is_limited() {
+        let mut simulator = PageSimulator::new(TestPage).unwrap();
+        let err = simulator.send(Msg::Loop(Capture::default())).unwrap_err();
+
+        assert_eq!(err.code(), "simulator");
+        assert_eq!(simulator.model().loops, MAX_MESSAGES_PER_SEND);
+    }
+
+    #[test]
+    fn missing_listener_is_an_error() {
+        let mut simulator = PageSimulator::new(TestPage).unwrap();
+        let err = simulator.click(Id).unwrap_err();
+
+        assert_eq!(err.code(), "simulator");
+    }
+}
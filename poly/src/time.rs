@@ -2,7 +2,7 @@ use std::convert::TryInto;
 use std::ops::Sub;
 use std::time;
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, serde::Serialize, serde::Deserialize)]
 pub struct Posix {
     milliseconds: i128,
 }
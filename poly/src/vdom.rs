@@ -0,0 +1,1659 @@
+use crate::markup::syntax::escape_attribute;
+use crate::markup::syntax::escape_text;
+use crate::markup::syntax::ESCAPABLE_RAW_TEXT_ELEMENTS;
+use crate::markup::syntax::RAW_TEXT_ELEMENTS;
+use crate::markup::syntax::VOID_ELEMENTS;
+use entities::ENTITIES;
+
+const TABLE_CONTEXT: &[&str] = &["table", "tbody", "tfoot", "thead", "tr"];
+
+const TABLE_PARTS: &[&str] = &[
+    "caption", "col", "colgroup", "tbody", "td", "tfoot", "th", "thead", "tr",
+];
+
+const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+const IMPLIED_END_TAGS: &[&str] = &[
+    "dd", "dt", "li", "optgroup", "option", "p", "rb", "rp", "rt", "rtc",
+];
+
+const CLOSES_P: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "center",
+    "details",
+    "dialog",
+    "dir",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "listing",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "summary",
+    "ul",
+];
+
+const SPECIAL: &[&str] = &[
+    "address",
+    "applet",
+    "area",
+    "article",
+    "aside",
+    "base",
+    "basefont",
+    "bgsound",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "caption",
+    "center",
+    "col",
+    "colgroup",
+    "dd",
+    "details",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "frame",
+    "frameset",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "iframe",
+    "img",
+    "input",
+    "keygen",
+    "li",
+    "link",
+    "listing",
+    "main",
+    "marquee",
+    "menu",
+    "meta",
+    "nav",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "ol",
+    "p",
+    "param",
+    "plaintext",
+    "pre",
+    "script",
+    "search",
+    "section",
+    "select",
+    "source",
+    "style",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "track",
+    "ul",
+    "wbr",
+    "xmp",
+];
+
+// HTML start tags that end svg or math content
+const BREAKOUT_TAGS: &[&str] = &[
+    "b",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "center",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "listing",
+    "menu",
+    "meta",
+    "nobr",
+    "ol",
+    "p",
+    "pre",
+    "ruby",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "strike",
+    "sub",
+    "sup",
+    "table",
+    "tt",
+    "u",
+    "ul",
+    "var",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Namespace {
+    Html,
+    Svg,
+    MathMl,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+    Comment(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Element {
+    pub tag: String,
+    pub namespace: Namespace,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    fn new(tag: &str) -> Element {
+        Element {
+            tag: tag.to_string(),
+            namespace: Namespace::Html,
+            attributes: vec![],
+            children: vec![],
+        }
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.attribute("id")
+    }
+
+    fn is_html(&self, tags: &[&str]) -> bool {
+        self.namespace == Namespace::Html && tags.contains(&self.tag.as_str())
+    }
+
+    fn is_raw_text(&self) -> bool {
+        self.is_html(RAW_TEXT_ELEMENTS)
+    }
+
+    fn is_void(&self) -> bool {
+        self.is_html(VOID_ELEMENTS)
+    }
+
+    fn is_html_integration_point(&self) -> bool {
+        self.namespace == Namespace::Svg
+            && ["foreignObject", "desc", "title"]
+                .iter()
+                .any(|tag| self.tag.eq_ignore_ascii_case(tag))
+    }
+
+    fn is_mathml_text_integration_point(&self) -> bool {
+        self.namespace == Namespace::MathMl
+            && ["mi", "mo", "mn", "ms", "mtext"].contains(&self.tag.as_str())
+    }
+}
+
+impl Node {
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html, false);
+        html
+    }
+
+    fn write_html(&self, html: &mut String, raw_text: bool) {
+        match self {
+            Node::Text(text) if raw_text => html.push_str(text),
+
+            Node::Text(text) => html.push_str(&escape_text(text)),
+
+            Node::Comment(text) => html.push_str(&format!("<!--{}-->", text)),
+
+            Node::Element(element) => {
+                html.push_str(&format!("<{}", element.tag));
+
+                for (name, value) in &element.attributes {
+                    html.push_str(&format!(" {}=\"{}\"", name, escape_attribute(value)));
+                }
+
+                html.push('>');
+
+                if element.is_void() {
+                    return;
+                }
+
+                for child in &element.children {
+                    child.write_html(html, element.is_raw_text());
+                }
+
+                html.push_str(&format!("</{}>", element.tag));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Target {
+    pub element_id: Option<String>,
+    pub path: Vec<usize>,
+}
+
+impl Target {
+    fn root() -> Target {
+        Target {
+            element_id: None,
+            path: vec![],
+        }
+    }
+
+    fn element(id: &str) -> Target {
+        Target {
+            element_id: Some(id.to_string()),
+            path: vec![],
+        }
+    }
+
+    fn child(&self, index: usize) -> Target {
+        let mut path = self.path.clone();
+        path.push(index);
+
+        Target {
+            element_id: self.element_id.clone(),
+            path,
+        }
+    }
+}
+
+// Paths index into `childNodes`, so text and comment nodes are counted.
+// `Insert` places the node before the child currently at `index`, or appends it.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", content = "config")]
+#[serde(rename_all = "camelCase")]
+pub enum Patch {
+    ReplaceChildren {
+        target: Target,
+        html: String,
+    },
+    Replace {
+        target: Target,
+        html: String,
+    },
+    Insert {
+        target: Target,
+        index: usize,
+        html: String,
+    },
+    Remove {
+        target: Target,
+    },
+    SetAttribute {
+        target: Target,
+        name: String,
+        value: String,
+    },
+    RemoveAttribute {
+        target: Target,
+        name: String,
+    },
+    SetText {
+        target: Target,
+        text: String,
+    },
+}
+
+#[derive(Default)]
+pub struct VirtualDom {
+    nodes: Option<Vec<Node>>,
+}
+
+impl VirtualDom {
+    pub fn new() -> VirtualDom {
+        VirtualDom { nodes: None }
+    }
+
+    pub fn patch(&mut self, html: &str) -> Vec<Patch> {
+        let nodes = parse(html);
+
+        let patches = match &self.nodes {
+            Some(old_nodes) => diff(old_nodes, &nodes),
+
+            None => vec![Patch::ReplaceChildren {
+                target: Target::root(),
+                html: html.to_string(),
+            }],
+        };
+
+        self.nodes = Some(nodes);
+        patches
+    }
+
+    // Records html that was rendered without patches, so the next diff starts from it
+    pub fn set_html(&mut self, html: &str) {
+        self.nodes = Some(parse(html));
+    }
+
+    pub fn reset(&mut self) {
+        self.nodes = None;
+    }
+}
+
+pub fn diff(old: &[Node], new: &[Node]) -> Vec<Patch> {
+    let mut patches = vec![];
+    diff_children(&Target::root(), old, new, &mut patches);
+    patches
+}
+
+// Unchanged nodes at the start and end are skipped, so inserting or removing a single node
+// anywhere produces one patch. There are no keyed moves: when nodes in the middle are
+// reordered they are patched pairwise by position.
+fn diff_children(target: &Target, old: &[Node], new: &[Node], patches: &mut Vec<Patch>) {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old_node, new_node)| old_node == new_node)
+        .count();
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old_node, new_node)| old_node == new_node)
+        .count();
+
+    let old = &old[prefix..old.len() - suffix];
+    let new = &new[prefix..new.len() - suffix];
+
+    for (index, (old_node, new_node)) in old.iter().zip(new).enumerate() {
+        diff_node(&target.child(prefix + index), old_node, new_node, patches);
+    }
+
+    // Remove from the end so the remaining indexes stay valid
+    for index in (new.len()..old.len()).rev() {
+        patches.push(Patch::Remove {
+            target: target.child(prefix + index),
+        });
+    }
+
+    for (index, node) in new.iter().enumerate().skip(old.len()) {
+        patches.push(Patch::Insert {
+            target: target.clone(),
+            index: prefix + index,
+            html: node.to_html(),
+        });
+    }
+}
+
+fn diff_node(target: &Target, old: &Node, new: &Node, patches: &mut Vec<Patch>) {
+    match (old, new) {
+        (Node::Text(old_text), Node::Text(new_text))
+        | (Node::Comment(old_text), Node::Comment(new_text)) => {
+            if old_text != new_text {
+                patches.push(Patch::SetText {
+                    target: target.clone(),
+                    text: new_text.clone(),
+                });
+            }
+        }
+
+        (Node::Element(old_element), Node::Element(new_element))
+            if old_element.tag == new_element.tag
+                && old_element.namespace == new_element.namespace =>
+        {
+            diff_element(target, old_element, new_element, patches)
+        }
+
+        _ => patches.push(Patch::Replace {
+            target: target.clone(),
+            html: new.to_html(),
+        }),
+    }
+}
+
+fn diff_element(target: &Target, old: &Element, new: &Element, patches: &mut Vec<Patch>) {
+    // Address the children by id when the element keeps the same id
+    let children_target = match (old.id(), new.id()) {
+        (Some(old_id), Some(new_id)) if old_id == new_id => Target::element(new_id),
+        _ => target.clone(),
+    };
+
+    for (name, value) in &new.attributes {
+        if old.attribute(name) != Some(value) {
+            patches.push(Patch::SetAttribute {
+                target: target.clone(),
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+
+    for (name, _) in &old.attributes {
+        if new.attribute(name).is_none() {
+            patches.push(Patch::RemoveAttribute {
+                target: target.clone(),
+                name: name.clone(),
+            });
+        }
+    }
+
+    diff_children(&children_target, &old.children, &new.children, patches);
+}
+
+// Builds the tree the browser would build when setting `innerHTML` on the body: comments are
+// kept, entities are decoded, and tables, optional end tags and misplaced block elements are
+// fixed up the same way. Misnested formatting elements (`<b><i></b></i>`) are closed in order
+// instead of running the adoption agency algorithm, and svg/math names keep their source case.
+pub fn parse(html: &str) -> Vec<Node> {
+    let html = html.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut parser = Parser {
+        input: &html,
+        pos: 0,
+        stack: vec![Open {
+            element: Element::new("body"),
+            foster: false,
+        }],
+        skip_newline: false,
+    };
+
+    while let Some(token) = parser.next_token() {
+        let skip_newline = std::mem::take(&mut parser.skip_newline);
+
+        match token {
+            Token::StartTag(tag) => parser.start_tag(tag),
+            Token::EndTag(name) => parser.end_tag(&name),
+            Token::Comment(text) => parser.append(Node::Comment(text)),
+
+            Token::Text(text) => {
+                let text = match text.strip_prefix('\n') {
+                    Some(text) if skip_newline => text.to_string(),
+                    _ => text,
+                };
+
+                parser.text(text);
+            }
+        }
+    }
+
+    while parser.stack.len() > 1 {
+        parser.pop();
+    }
+
+    parser.stack.remove(0).element.children
+}
+
+enum Token {
+    StartTag(Tag),
+    EndTag(String),
+    Text(String),
+    Comment(String),
+}
+
+struct Tag {
+    name: String,
+    attributes: Vec<(String, String)>,
+    self_closing: bool,
+}
+
+struct Open {
+    element: Element,
+    foster: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Scope {
+    Default,
+    ListItem,
+    Button,
+    Table,
+}
+
+impl Scope {
+    fn is_boundary(self, element: &Element) -> bool {
+        let default = [
+            "applet", "caption", "html", "table", "td", "th", "marquee", "object", "template",
+        ];
+
+        match self {
+            Scope::Table => element.is_html(&["html", "table", "template"]),
+
+            _ => {
+                element.is_html(&default)
+                    || element.is_html_integration_point()
+                    || element.is_mathml_text_integration_point()
+                    || matches!(self, Scope::ListItem) && element.is_html(&["ol", "ul"])
+                    || matches!(self, Scope::Button) && element.is_html(&["button"])
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    stack: Vec<Open>,
+    skip_newline: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn current(&self) -> &Element {
+        &self.stack[self.stack.len() - 1].element
+    }
+
+    fn current_is(&self, tags: &[&str]) -> bool {
+        self.current().is_html(tags)
+    }
+
+    fn in_foreign_content(&self) -> bool {
+        let current = self.current();
+        current.namespace != Namespace::Html && !current.is_html_integration_point()
+    }
+
+    fn in_scope(&self, tags: &[&str], scope: Scope) -> bool {
+        for open in self.stack[1..].iter().rev() {
+            if open.element.is_html(tags) {
+                return true;
+            } else if scope.is_boundary(&open.element) {
+                return false;
+            }
+        }
+
+        false
+    }
+
+    fn append(&mut self, node: Node) {
+        let index = self.stack.len() - 1;
+        append_child(&mut self.stack[index].element.children, node);
+    }
+
+    // Content that is not allowed inside a table is moved in front of it
+    fn foster(&mut self, node: Node) {
+        let table = self.stack[1..]
+            .iter()
+            .rposition(|open| open.element.is_html(&["table"]))
+            .map_or(0, |index| index + 1);
+
+        match table {
+            0 => self.append(node),
+            table => append_child(&mut self.stack[table - 1].element.children, node),
+        }
+    }
+
+    fn push(&mut self, element: Element, foster: bool) {
+        self.stack.push(Open { element, foster });
+    }
+
+    fn insert_leaf(&mut self, element: Element, foster: bool) {
+        if foster {
+            self.foster(Node::Element(element));
+        } else {
+            self.append(Node::Element(element));
+        }
+    }
+
+    fn pop(&mut self) {
+        if self.stack.len() <= 1 {
+            return;
+        }
+
+        if let Some(open) = self.stack.pop() {
+            if open.foster {
+                self.foster(Node::Element(open.element));
+            } else {
+                self.append(Node::Element(open.element));
+            }
+        }
+    }
+
+    fn pop_until(&mut self, tags: &[&str]) {
+        while self.stack.len() > 1 {
+            let done = self.current_is(tags);
+            self.pop();
+
+            if done {
+                break;
+            }
+        }
+    }
+
+    fn generate_implied_end_tags(&mut self, except: &str) {
+        while self.current_is(IMPLIED_END_TAGS) && self.current().tag != except {
+            self.pop();
+        }
+    }
+
+    fn close_p(&mut self) {
+        if self.in_scope(&["p"], Scope::Button) {
+            self.generate_implied_end_tags("p");
+            self.pop_until(&["p"]);
+        }
+    }
+
+    fn text(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+
+        let is_whitespace = text.chars().all(|c| c.is_ascii_whitespace());
+
+        if self.current_is(&["colgroup"]) && !is_whitespace {
+            self.pop();
+            self.text(text);
+        } else if self.current_is(TABLE_CONTEXT) && !is_whitespace {
+            self.foster(Node::Text(text));
+        } else {
+            self.append(Node::Text(text));
+        }
+    }
+
+    fn start_tag(&mut self, tag: Tag) {
+        let name = tag.name.to_ascii_lowercase();
+
+        if self.in_foreign_content() {
+            let is_breakout = BREAKOUT_TAGS.contains(&name.as_str())
+                || name == "font"
+                    && tag
+                        .attributes
+                        .iter()
+                        .any(|(key, _)| ["color", "face", "size"].contains(&key.as_str()));
+
+            let is_html = self.current().is_mathml_text_integration_point()
+                && name != "mglyph"
+                && name != "malignmark";
+
+            if !is_breakout && !is_html {
+                let namespace = self.current().namespace;
+                return self.foreign_start_tag(tag, namespace, false);
+            }
+
+            while self.in_foreign_content() && !self.current().is_mathml_text_integration_point() {
+                self.pop();
+            }
+        }
+
+        self.html_start_tag(&name, tag)
+    }
+
+    fn foreign_start_tag(&mut self, tag: Tag, namespace: Namespace, foster: bool) {
+        let element = Element {
+            tag: tag.name,
+            namespace,
+            attributes: dedupe(tag.attributes),
+            children: vec![],
+        };
+
+        if tag.self_closing {
+            self.insert_leaf(element, foster);
+        } else {
+            self.push(element, foster);
+        }
+    }
+
+    fn html_start_tag(&mut self, name: &str, tag: Tag) {
+        if self.current_is(&["colgroup"]) {
+            if name == "col" {
+                return self.insert_leaf(html_element(name, tag.attributes), false);
+            }
+
+            self.pop();
+        }
+
+        if TABLE_PARTS.contains(&name) {
+            if self.in_scope(&["td", "th"], Scope::Table) {
+                self.generate_implied_end_tags("");
+                self.pop_until(&["td", "th"]);
+                return self.html_start_tag(name, tag);
+            }
+
+            if self.in_scope(&["caption"], Scope::Table) {
+                self.generate_implied_end_tags("");
+                self.pop_until(&["caption"]);
+                return self.html_start_tag(name, tag);
+            }
+        }
+
+        let mut foster = false;
+
+        if self.current_is(TABLE_CONTEXT) {
+            let section = if self.current_is(&["table"]) {
+                None
+            } else {
+                Some(self.current().tag.clone())
+            };
+
+            match (section.as_deref(), name) {
+                (None, "caption" | "colgroup" | "tbody" | "tfoot" | "thead")
+                | (Some("tbody" | "tfoot" | "thead"), "tr")
+                | (Some("tr"), "td" | "th") => {
+                    return self.push(html_element(name, tag.attributes), false);
+                }
+
+                (None, "col") => {
+                    self.push(Element::new("colgroup"), false);
+                    return self.html_start_tag(name, tag);
+                }
+
+                (None, "tr" | "td" | "th") => {
+                    self.push(Element::new("tbody"), false);
+                    return self.html_start_tag(name, tag);
+                }
+
+                (Some("tbody" | "tfoot" | "thead"), "td" | "th") => {
+                    self.push(Element::new("tr"), false);
+                    return self.html_start_tag(name, tag);
+                }
+
+                (None, "table") => {
+                    self.pop_until(&["table"]);
+                    return self.html_start_tag(name, tag);
+                }
+
+                (Some(_), "caption" | "col" | "colgroup" | "tbody" | "tfoot" | "thead")
+                | (Some(_), "table")
+                | (Some("tr"), "tr") => {
+                    self.pop();
+                    return self.html_start_tag(name, tag);
+                }
+
+                (_, "style" | "script" | "template") => {}
+
+                (_, "input")
+                    if tag.attributes.iter().any(|(key, value)| {
+                        key.eq_ignore_ascii_case("type") && value.eq_ignore_ascii_case("hidden")
+                    }) =>
+                {
+                    return self.insert_leaf(html_element(name, tag.attributes), false);
+                }
+
+                _ => foster = true,
+            }
+        }
+
+        self.body_start_tag(name, tag, foster)
+    }
+
+    fn body_start_tag(&mut self, name: &str, tag: Tag, foster: bool) {
+        let name = if name == "image" { "img" } else { name };
+
+        match name {
+            "html" | "head" | "body" | "frameset" | "frame" => return,
+            _ if TABLE_PARTS.contains(&name) => return,
+            "form"
+                if self
+                    .stack
+                    .iter()
+                    .any(|open| open.element.is_html(&["form"])) =>
+            {
+                return
+            }
+            _ => {}
+        }
+
+        match name {
+            "li" | "dd" | "dt" => {
+                let closes: &[&str] = if name == "li" { &["li"] } else { &["dd", "dt"] };
+
+                for index in (1..self.stack.len()).rev() {
+                    let element = &self.stack[index].element;
+
+                    if element.is_html(closes) {
+                        let tag = element.tag.clone();
+                        self.generate_implied_end_tags(&tag);
+                        self.pop_until(&[&tag]);
+                        break;
+                    }
+
+                    if element.is_html(SPECIAL) && !element.is_html(&["address", "div", "p"]) {
+                        break;
+                    }
+                }
+
+                self.close_p();
+            }
+
+            "button" if self.in_scope(&["button"], Scope::Default) => {
+                self.generate_implied_end_tags("");
+                self.pop_until(&["button"]);
+            }
+
+            "a" | "nobr" if self.in_scope(&[name], Scope::Default) => {
+                self.pop_until(&[name]);
+            }
+
+            "option" if self.current_is(&["option"]) => self.pop(),
+
+            "optgroup" => {
+                if self.current_is(&["option"]) {
+                    self.pop();
+                }
+
+                if self.current_is(&["optgroup"]) {
+                    self.pop();
+                }
+            }
+
+            "rb" | "rtc" if self.in_scope(&["ruby"], Scope::Default) => {
+                self.generate_implied_end_tags("");
+            }
+
+            "rp" | "rt" if self.in_scope(&["ruby"], Scope::Default) => {
+                self.generate_implied_end_tags("rtc");
+            }
+
+            "form" | "hr" | "table" | "xmp" => self.close_p(),
+
+            _ if CLOSES_P.contains(&name) => {
+                self.close_p();
+
+                if HEADINGS.contains(&name) && self.current_is(HEADINGS) {
+                    self.pop();
+                }
+            }
+
+            _ => {}
+        }
+
+        match name {
+            "svg" => self.foreign_start_tag(tag, Namespace::Svg, foster),
+
+            "math" => self.foreign_start_tag(tag, Namespace::MathMl, foster),
+
+            _ if VOID_ELEMENTS.contains(&name) => {
+                self.insert_leaf(html_element(name, tag.attributes), foster);
+            }
+
+            _ if RAW_TEXT_ELEMENTS.contains(&name)
+                || ESCAPABLE_RAW_TEXT_ELEMENTS.contains(&name) =>
+            {
+                let mut element = html_element(name, tag.attributes);
+                let text = self.raw_text(name);
+
+                let text = if RAW_TEXT_ELEMENTS.contains(&name) {
+                    text
+                } else {
+                    decode_entities(&text, false)
+                };
+
+                let text = match text.strip_prefix('\n') {
+                    Some(text) if name == "textarea" => text.to_string(),
+                    _ => text,
+                };
+
+                append_child(&mut element.children, Node::Text(text));
+                self.insert_leaf(element, foster);
+            }
+
+            _ => {
+                self.skip_newline = name == "pre" || name == "listing";
+                self.push(html_element(name, tag.attributes), foster);
+            }
+        }
+    }
+
+    fn end_tag(&mut self, name: &str) {
+        let name = name.to_ascii_lowercase();
+        let name = name.as_str();
+
+        if self.current().namespace != Namespace::Html {
+            for index in (1..self.stack.len()).rev() {
+                let element = &self.stack[index].element;
+
+                if element.namespace == Namespace::Html {
+                    break;
+                }
+
+                if element.tag.eq_ignore_ascii_case(name) {
+                    while self.stack.len() > index {
+                        self.pop();
+                    }
+
+                    return;
+                }
+            }
+        }
+
+        match name {
+            "html" | "body" => {}
+
+            "br" => self.insert_leaf(Element::new("br"), self.current_is(TABLE_CONTEXT)),
+
+            "p" => {
+                if !self.in_scope(&["p"], Scope::Button) {
+                    self.push(Element::new("p"), self.current_is(TABLE_CONTEXT));
+                }
+
+                self.close_p();
+            }
+
+            "li" => {
+                if self.in_scope(&["li"], Scope::ListItem) {
+                    self.generate_implied_end_tags("li");
+                    self.pop_until(&["li"]);
+                }
+            }
+
+            _ if HEADINGS.contains(&name) => {
+                if self.in_scope(HEADINGS, Scope::Default) {
+                    self.generate_implied_end_tags("");
+                    self.pop_until(HEADINGS);
+                }
+            }
+
+            "table" | "caption" | "colgroup" | "tbody" | "tfoot" | "thead" | "tr" | "td" | "th" => {
+                if self.in_scope(&[name], Scope::Table) {
+                    self.generate_implied_end_tags("");
+                    self.pop_until(&[name]);
+                }
+            }
+
+            _ if SPECIAL.contains(&name) => {
+                if self.in_scope(&[name], Scope::Default) {
+                    self.generate_implied_end_tags(name);
+                    self.pop_until(&[name]);
+                }
+            }
+
+            _ => {
+                for index in (1..self.stack.len()).rev() {
+                    let element = &self.stack[index].element;
+
+                    if element.is_html(&[name]) {
+                        self.generate_implied_end_tags(name);
+
+                        while self.stack.len() > index {
+                            self.pop();
+                        }
+
+                        return;
+                    }
+
+                    if element.is_html(SPECIAL) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        loop {
+            let rest = self.rest();
+            let mut chars = rest.chars();
+
+            match (chars.next()?, chars.next()) {
+                ('<', Some('!')) if rest.starts_with("<!--") => {
+                    self.pos += 4;
+                    return Some(Token::Comment(self.comment()));
+                }
+
+                ('<', Some('!'))
+                    if rest.starts_with("<![CDATA[")
+                        && self.current().namespace != Namespace::Html =>
+                {
+                    self.pos += 9;
+                    return Some(Token::Text(self.take_until("]]>").to_string()));
+                }
+
+                ('<', Some('!')) if starts_with_ignore_case(rest, "<!doctype") => {
+                    self.take_until(">");
+                }
+
+                ('<', Some('!')) => {
+                    self.pos += 2;
+                    return Some(Token::Comment(self.take_until(">").to_string()));
+                }
+
+                ('<', Some('?')) => {
+                    self.pos += 1;
+                    return Some(Token::Comment(self.take_until(">").to_string()));
+                }
+
+                ('<', Some('/')) => match chars.next() {
+                    Some(c) if c.is_ascii_alphabetic() => {
+                        self.pos += 2;
+
+                        if let Some(name) = self.end_tag_name() {
+                            return Some(Token::EndTag(name));
+                        }
+                    }
+
+                    Some('>') => self.pos += 3,
+
+                    Some(_) => {
+                        self.pos += 2;
+                        return Some(Token::Comment(self.take_until(">").to_string()));
+                    }
+
+                    None => {
+                        self.pos += 2;
+                        return Some(Token::Text("</".to_string()));
+                    }
+                },
+
+                ('<', Some(c)) if c.is_ascii_alphabetic() => {
+                    self.pos += 1;
+
+                    if let Some(tag) = self.tag() {
+                        return Some(Token::StartTag(tag));
+                    }
+                }
+
+                (c, _) => {
+                    let start = self.pos;
+                    self.pos += c.len_utf8();
+                    self.take_while(|c| c != '<');
+
+                    let text = &self.input[start..self.pos];
+                    return Some(Token::Text(decode_entities(text, false)));
+                }
+            }
+        }
+    }
+
+    fn comment(&mut self) -> String {
+        for empty in ["->", ">"] {
+            if self.rest().starts_with(empty) {
+                self.pos += empty.len();
+                return String::new();
+            }
+        }
+
+        let rest = self.rest();
+        let end = ["-->", "--!>"]
+            .iter()
+            .filter_map(|end| rest.find(end).map(|index| (index, end.len())))
+            .min();
+
+        match end {
+            Some((index, len)) => {
+                self.pos += index + len;
+                rest[..index].to_string()
+            }
+
+            None => {
+                self.pos = self.input.len();
+                rest.to_string()
+            }
+        }
+    }
+
+    // Returns None when the input ends inside the tag, which drops it like a browser does
+    fn tag(&mut self) -> Option<Tag> {
+        let name = self
+            .take_while(|c| !c.is_ascii_whitespace() && c != '/' && c != '>')
+            .to_string();
+
+        let mut attributes = vec![];
+        let mut self_closing = false;
+
+        loop {
+            self.take_while(|c| c.is_ascii_whitespace());
+            let rest = self.rest();
+
+            if rest.is_empty() {
+                return None;
+            } else if rest.starts_with("/>") {
+                self.pos += 2;
+                self_closing = true;
+                break;
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            } else if rest.starts_with('/') {
+                self.pos += 1;
+            } else {
+                attributes.push(self.attribute());
+            }
+        }
+
+        Some(Tag {
+            name,
+            attributes,
+            self_closing,
+        })
+    }
+
+    fn end_tag_name(&mut self) -> Option<String> {
+        let name = self
+            .take_while(|c| !c.is_ascii_whitespace() && c != '/' && c != '>')
+            .to_string();
+
+        if self.rest().find('>').is_none() {
+            self.pos = self.input.len();
+            return None;
+        }
+
+        self.take_until(">");
+        Some(name)
+    }
+
+    fn attribute(&mut self) -> (String, String) {
+        let start = self.pos;
+        self.pos += self.rest().chars().next().map_or(0, char::len_utf8);
+        self.take_while(|c| !c.is_ascii_whitespace() && c != '=' && c != '>' && c != '/');
+        let name = self.input[start..self.pos].to_string();
+
+        self.take_while(|c| c.is_ascii_whitespace());
+
+        if !self.rest().starts_with('=') {
+            return (name, String::new());
+        }
+
+        self.pos += 1;
+        self.take_while(|c| c.is_ascii_whitespace());
+
+        let value = match self.rest().chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let value = self.take_while(|c| c != quote);
+                self.pos = (self.pos + 1).min(self.input.len());
+                value
+            }
+
+            _ => self.take_while(|c| !c.is_ascii_whitespace() && c != '>'),
+        };
+
+        (name, decode_entities(value, true))
+    }
+
+    // Reads the content of a raw text element and consumes its end tag
+    fn raw_text(&mut self, tag: &str) -> String {
+        let rest = self.rest();
+        let lowercase = rest.to_ascii_lowercase();
+        let closing = format!("</{}", tag);
+        let mut search = 0;
+
+        while let Some(index) = lowercase[search..].find(&closing) {
+            let index = search + index;
+            let after = lowercase[index + closing.len()..].chars().next();
+
+            if matches!(after, Some(c) if c.is_ascii_whitespace() || c == '/' || c == '>') {
+                self.pos += index;
+                self.take_until(">");
+                return rest[..index].to_string();
+            }
+
+            search = index + closing.len();
+        }
+
+        self.pos = self.input.len();
+        rest.to_string()
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+
+        while let Some(c) = self.rest().chars().next() {
+            if !predicate(c) {
+                break;
+            }
+
+            self.pos += c.len_utf8();
+        }
+
+        &self.input[start..self.pos]
+    }
+
+    // Returns the text before the pattern and moves past it
+    fn take_until(&mut self, pattern: &str) -> &'a str {
+        let rest = self.rest();
+
+        match rest.find(pattern) {
+            Some(index) => {
+                self.pos += index + pattern.len();
+                &rest[..index]
+            }
+
+            None => {
+                self.pos = self.input.len();
+                rest
+            }
+        }
+    }
+}
+
+fn html_element(tag: &str, attributes: Vec<(String, String)>) -> Element {
+    let attributes = attributes
+        .into_iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value))
+        .collect();
+
+    Element {
+        attributes: dedupe(attributes),
+        ..Element::new(tag)
+    }
+}
+
+// Browsers keep the first of duplicate attributes
+fn dedupe(attributes: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut deduped: Vec<(String, String)> = vec![];
+
+    for (name, value) in attributes {
+        if !deduped.iter().any(|(key, _)| *key == name) {
+            deduped.push((name, value));
+        }
+    }
+
+    deduped
+}
+
+// Browsers merge adjacent text
+fn append_child(children: &mut Vec<Node>, node: Node) {
+    match (children.last_mut(), node) {
+        (_, Node::Text(text)) if text.is_empty() => {}
+        (Some(Node::Text(last)), Node::Text(text)) => last.push_str(&text),
+        (_, node) => children.push(node),
+    }
+}
+
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+fn decode_entities(s: &str, in_attribute: bool) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        match decode_reference(rest, in_attribute) {
+            Some((text, len)) => {
+                decoded.push_str(&text);
+                rest = &rest[len..];
+            }
+
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+// Decodes the character reference at the start of `s`, returning it and its length
+fn decode_reference(s: &str, in_attribute: bool) -> Option<(String, usize)> {
+    if let Some(numeric) = s.strip_prefix("&#") {
+        let (digits, radix, prefix_len) = match numeric.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 3),
+            None => (numeric, 10, 2),
+        };
+
+        let len = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+
+        if len == 0 {
+            return None;
+        }
+
+        let value = u32::from_str_radix(&digits[..len], radix).unwrap_or(u32::MAX);
+        let semicolon = usize::from(digits[len..].starts_with(';'));
+
+        return Some((
+            numeric_reference(value).to_string(),
+            prefix_len + len + semicolon,
+        ));
+    }
+
+    if !s[1..].starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let entity = ENTITIES
+        .iter()
+        .filter(|entity| s.starts_with(entity.entity))
+        .max_by_key(|entity| entity.entity.len())?;
+
+    let len = entity.entity.len();
+
+    // Legacy entities without a semicolon are left alone in attributes, i.e. "?a=1&copy=2"
+    if in_attribute && !entity.entity.ends_with(';') {
+        let next = s[len..].chars().next();
+
+        if matches!(next, Some(c) if c == '=' || c.is_ascii_alphanumeric()) {
+            return None;
+        }
+    }
+
+    Some((entity.characters.to_string(), len))
+}
+
+fn numeric_reference(value: u32) -> char {
+    const WINDOWS_1252: [u32; 32] = [
+        0x20AC, 0x81, 0x201A, 0x0192, 0x201E, 0x2026, 0x2020, 0x2021, 0x02C6, 0x2030, 0x0160,
+        0x2039, 0x0152, 0x8D, 0x017D, 0x8F, 0x90, 0x2018, 0x2019, 0x201C, 0x201D, 0x2022, 0x2013,
+        0x2014, 0x02DC, 0x2122, 0x0161, 0x203A, 0x0153, 0x9D, 0x017E, 0x0178,
+    ];
+
+    let value = match value {
+        0x80..=0x9F => WINDOWS_1252[(value - 0x80) as usize],
+        value => value,
+    };
+
+    match value {
+        0 => char::REPLACEMENT_CHARACTER,
+        value => char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str, children: Vec<Node>) -> Node {
+        Node::Element(Element {
+            children,
+            ..Element::new(tag)
+        })
+    }
+
+    fn text(text: &str) -> Node {
+        Node::Text(text.to_string())
+    }
+
+    fn html(nodes: &[Node]) -> String {
+        nodes.iter().map(Node::to_html).collect()
+    }
+
+    #[test]
+    fn parse_adds_implicit_tbody() {
+        assert_eq!(
+            html(&parse("<table><tr><td>1</td></tr></table>")),
+            "<table><tbody><tr><td>1</td></tr></tbody></table>"
+        );
+
+        assert_eq!(
+            html(&parse("<table><td>1</table>")),
+            "<table><tbody><tr><td>1</td></tr></tbody></table>"
+        );
+
+        assert_eq!(
+            html(&parse("<table><col></table>")),
+            "<table><colgroup><col></colgroup></table>"
+        );
+    }
+
+    #[test]
+    fn parse_fosters_content_out_of_tables() {
+        assert_eq!(
+            html(&parse("<table>text<tr><td>1</td></tr></table>")),
+            "text<table><tbody><tr><td>1</td></tr></tbody></table>"
+        );
+
+        assert_eq!(
+            html(&parse("<table><div>x</div></table>")),
+            "<div>x</div><table></table>"
+        );
+    }
+
+    #[test]
+    fn parse_closes_optional_end_tags() {
+        assert_eq!(
+            html(&parse("<ul><li>a<li>b</ul>")),
+            "<ul><li>a</li><li>b</li></ul>"
+        );
+
+        assert_eq!(html(&parse("<p>a<p>b")), "<p>a</p><p>b</p>");
+
+        assert_eq!(
+            html(&parse("<p><div></div></p>")),
+            "<p></p><div></div><p></p>"
+        );
+
+        assert_eq!(
+            html(&parse("<dl><dt>a<dd>b<dt>c</dl>")),
+            "<dl><dt>a</dt><dd>b</dd><dt>c</dt></dl>"
+        );
+
+        assert_eq!(
+            html(&parse("<select><option>a<option>b</select>")),
+            "<select><option>a</option><option>b</option></select>"
+        );
+    }
+
+    #[test]
+    fn parse_ignores_self_closing_flag_on_html_elements() {
+        assert_eq!(html(&parse("<div/>text")), "<div>text</div>");
+
+        assert_eq!(
+            html(&parse("<svg><path d=\"M0\"/><circle/></svg>")),
+            "<svg><path d=\"M0\"></path><circle></circle></svg>"
+        );
+    }
+
+    #[test]
+    fn parse_keeps_comments() {
+        assert_eq!(
+            parse("a<!-- b -->c"),
+            vec![text("a"), Node::Comment(" b ".to_string()), text("c")]
+        );
+
+        assert_eq!(parse("<!---->"), vec![Node::Comment(String::new())]);
+        assert_eq!(parse("<!-->"), vec![Node::Comment(String::new())]);
+        assert_eq!(parse("<!x>"), vec![Node::Comment("x".to_string())]);
+        assert_eq!(parse("<?php?>"), vec![Node::Comment("?php?".to_string())]);
+        assert_eq!(parse("</1>"), vec![Node::Comment("1".to_string())]);
+        assert_eq!(parse("<!DOCTYPE html>a"), vec![text("a")]);
+    }
+
+    #[test]
+    fn parse_decodes_entities() {
+        assert_eq!(parse("a&nbsp;b"), vec![text("a\u{a0}b")]);
+        assert_eq!(parse("&#169;&#xA9;&#XA9;"), vec![text("©©©")]);
+        assert_eq!(parse("&#128;&#0;"), vec![text("€\u{fffd}")]);
+        assert_eq!(parse("&notit;"), vec![text("¬it;")]);
+        assert_eq!(parse("&copy &amp &unknown;"), vec![text("© & &unknown;")]);
+        assert_eq!(parse("&lt;b&gt;"), vec![text("<b>")]);
+    }
+
+    #[test]
+    fn parse_keeps_legacy_entities_in_attributes() {
+        let nodes = parse("<a href=\"?a=1&copy=2&amp;b&copy;\"></a>");
+
+        match &nodes[0] {
+            Node::Element(element) => {
+                assert_eq!(element.attribute("href"), Some("?a=1&copy=2&b©"))
+            }
+            node => panic!("Unexpected node {:?}", node),
+        }
+    }
+
+    #[test]
+    fn parse_keeps_raw_text() {
+        assert_eq!(
+            parse("<script>if (a < b && c) {}</script>"),
+            vec![element("script", vec![text("if (a < b && c) {}")])]
+        );
+
+        assert_eq!(
+            parse("<textarea>\n&lt;b&gt;</textarea>"),
+            vec![element("textarea", vec![text("<b>")])]
+        );
+
+        assert_eq!(
+            html(&parse("<script>a</scripts></script>")),
+            "<script>a</scripts></script>"
+        );
+    }
+
+    #[test]
+    fn to_html_escapes_text_and_attributes() {
+        let html = "<div title=\"a &quot;b&quot; &amp; c\">1 &lt; 2 &amp; 3</div>";
+        assert_eq!(self::html(&parse(html)), html);
+    }
+
+    #[test]
+    fn diff_inserts_at_the_front() {
+        let old = parse("<ul><li>b</li><li>c</li></ul>");
+        let new = parse("<ul><li>a</li><li>b</li><li>c</li></ul>");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![Patch::Insert {
+                target: Target::root().child(0),
+                index: 0,
+                html: "<li>a</li>".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_removes_from_the_front() {
+        let old = parse("<p>a</p><p>b</p><p>c</p>");
+        let new = parse("<p>b</p><p>c</p>");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![Patch::Remove {
+                target: Target::root().child(0),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_targets_table_cells_through_tbody() {
+        let old = parse("<table><tr><td>1</td></tr></table>");
+        let new = parse("<table><tr><td>2</td></tr></table>");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![Patch::SetText {
+                target: Target {
+                    element_id: None,
+                    path: vec![0, 0, 0, 0, 0],
+                },
+                text: "2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_counts_comments_in_paths() {
+        let old = parse("<!--a--><p>1</p>");
+        let new = parse("<!--b--><p>2</p>");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Patch::SetText {
+                    target: Target::root().child(0),
+                    text: "b".to_string(),
+                },
+                Patch::SetText {
+                    target: Target::root().child(1).child(0),
+                    text: "2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_targets_children_by_id() {
+        let old = parse("<div id=\"list\"><p>1</p></div>");
+        let new = parse("<div id=\"list\"><p>2</p></div>");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![Patch::SetText {
+                target: Target::element("list").child(0).child(0),
+                text: "2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn virtual_dom_diffs_against_set_html() {
+        let mut dom = VirtualDom::new();
+
+        assert!(matches!(
+            dom.patch("<p>1</p>").as_slice(),
+            [Patch::ReplaceChildren { .. }]
+        ));
+
+        dom.set_html("<p>2</p>");
+
+        assert_eq!(
+            dom.patch("<p>2</p>"),
+            vec![],
+            "set_html should replace the previous tree"
+        );
+
+        dom.reset();
+
+        assert!(matches!(
+            dom.patch("<p>2</p>").as_slice(),
+            [Patch::ReplaceChildren { .. }]
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+use poly::browser::dom_id::DomId;
+use poly_macro::DomId;
+use std::fmt;
+
+#[derive(DomId)]
+enum Id {
+    SearchInput,
+    HTMLEditor,
+    #[dom_id(rename = "{main}")]
+    Main,
+    Row(usize),
+    Cell(usize, usize),
+    Field {
+        f: String,
+    },
+}
+
+#[derive(DomId)]
+#[dom_id(skip_display)]
+enum ManualId {
+    Sidebar,
+}
+
+impl fmt::Display for ManualId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManualId::Sidebar => write!(f, "manual-sidebar"),
+        }
+    }
+}
+
+#[test]
+fn display() {
+    assert_eq!(Id::SearchInput.to_string(), "search-input");
+    assert_eq!(Id::HTMLEditor.to_string(), "html-editor");
+    assert_eq!(Id::Row(3).to_string(), "row-3");
+    assert_eq!(Id::Cell(1, 2).to_string(), "cell-1-2");
+    assert_eq!(
+        Id::Field {
+            f: "name".to_string()
+        }
+        .to_string(),
+        "field-name"
+    );
+}
+
+#[test]
+fn rename_is_written_literally() {
+    assert_eq!(Id::Main.to_string(), "{main}");
+}
+
+#[test]
+fn skip_display_keeps_hand_written_impl() {
+    assert_eq!(ManualId::Sidebar.to_string(), "manual-sidebar");
+    assert_eq!(ManualId::Sidebar.selector().to_string(), "#manual-sidebar");
+}
+
+#[test]
+fn selector() {
+    assert_eq!(Id::Row(3).selector().to_string(), "#row-3");
+}
@@ -0,0 +1,82 @@
+use poly::route::Route;
+use poly::router::ParseRoute;
+use poly_macro::Route;
+
+#[derive(Clone, Debug, PartialEq, Route)]
+enum AppRoute {
+    #[route("/")]
+    Home,
+    #[route("/posts/:id")]
+    Post { id: u32 },
+    #[route("/search?q&page")]
+    Search { q: String, page: Option<u32> },
+    #[route("/files/:name/:version")]
+    File {
+        name: String,
+        version: Option<String>,
+    },
+    #[route("/redirect/:url?parser")]
+    Redirect { url: String, parser: String },
+}
+
+fn round_trip(route: AppRoute) {
+    let path = route.path();
+    assert_eq!(AppRoute::from_path(&path), Some(route), "{}", path);
+}
+
+#[test]
+fn paths() {
+    assert_eq!(AppRoute::Home.path(), "/");
+    assert_eq!(AppRoute::Post { id: 7 }.path(), "/posts/7");
+    assert_eq!(
+        AppRoute::Search {
+            q: "a b".to_string(),
+            page: None
+        }
+        .path(),
+        "/search?q=a%20b"
+    );
+}
+
+#[test]
+fn round_trips() {
+    round_trip(AppRoute::Home);
+    round_trip(AppRoute::Post { id: 42 });
+    round_trip(AppRoute::Search {
+        q: "rust & wasm".to_string(),
+        page: Some(2),
+    });
+    round_trip(AppRoute::Search {
+        q: "rust".to_string(),
+        page: None,
+    });
+    round_trip(AppRoute::File {
+        name: "report".to_string(),
+        version: Some("v2".to_string()),
+    });
+    round_trip(AppRoute::File {
+        name: "report".to_string(),
+        version: None,
+    });
+}
+
+#[test]
+fn fields_named_like_generated_locals() {
+    round_trip(AppRoute::Redirect {
+        url: "home".to_string(),
+        parser: "json".to_string(),
+    });
+}
+
+#[test]
+fn rejects_unknown_paths() {
+    assert_eq!(AppRoute::from_path("/posts/abc"), None);
+    assert_eq!(AppRoute::from_path("/posts/1/extra"), None);
+    assert_eq!(AppRoute::from_path("/search"), None);
+    assert_eq!(AppRoute::from_path("/missing"), None);
+}
+
+#[test]
+fn names() {
+    assert_eq!(AppRoute::Post { id: 1 }.name(), "Post");
+}
@@ -1,23 +1,566 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::parse::{Parse, ParseStream};
 use syn::DeriveInput;
-use syn::{parse_macro_input, Ident};
+use syn::{parse_macro_input, Data, Fields, Ident, Lit, LitStr, Meta, NestedMeta, Token};
 
-#[proc_macro_derive(DomId)]
+#[proc_macro_derive(DomId, attributes(dom_id))]
 pub fn dom_id_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
+
+    match expand_dom_id(&ast) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn expand_dom_id(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let name = &ast.ident;
 
-    TokenStream::from(quote! {
+    let variants = match &ast.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Ok(quote! {
+                impl DomId for #name {}
+            })
+        }
+    };
+
+    let mut seen_ids: Vec<String> = vec![];
+    let mut arms = vec![];
+
+    for variant in variants {
+        let variant_ident = &variant.ident;
+        let id = match parse_dom_id_rename(variant)? {
+            Some(rename) => rename,
+            None => to_kebab_case(&variant_ident.to_string()),
+        };
+
+        if seen_ids.contains(&id) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!("Duplicate dom id `{}`", id),
+            ));
+        }
+
+        seen_ids.push(id.clone());
+
+        let bindings: Vec<Ident> = (0..variant.fields.len())
+            .map(|i| Ident::new(&format!("__field_{}", i), proc_macro2::Span::call_site()))
+            .collect();
+
+        let pattern = match &variant.fields {
+            Fields::Unit => quote!(#name::#variant_ident),
+            Fields::Unnamed(_) => quote!(#name::#variant_ident(#(#bindings),*)),
+            Fields::Named(fields) => {
+                let field_idents = fields.named.iter().map(|field| &field.ident);
+                quote!(#name::#variant_ident { #(#field_idents: #bindings),* })
+            }
+        };
+
+        arms.push(quote! {
+            #pattern => {
+                __f.write_str(#id)?;
+                #(write!(__f, "-{}", #bindings)?;)*
+                Ok(())
+            }
+        });
+    }
+
+    if has_dom_id_flag(&ast.attrs, "skip_display")? {
+        return Ok(quote! {
+            impl DomId for #name {}
+        });
+    }
+
+    Ok(quote! {
         impl DomId for #name {}
+
+        impl std::fmt::Display for #name {
+            fn fmt(&self, __f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+fn has_dom_id_flag(attrs: &[syn::Attribute], flag: &str) -> syn::Result<bool> {
+    let mut found = false;
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("dom_id")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    format!("Expected #[dom_id({})]", flag),
+                ))
+            }
+        };
+
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident(flag) => found = true,
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        format!("Unknown dom_id attribute, expected {}", flag),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+fn parse_dom_id_rename(variant: &syn::Variant) -> syn::Result<Option<String>> {
+    let attr = match variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("dom_id"))
+    {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    let nested = match attr.parse_meta()? {
+        Meta::List(list) => list.nested,
+        meta => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "Expected #[dom_id(rename = \"...\")]",
+            ))
+        }
+    };
+
+    let mut rename = None;
+
+    for meta in nested {
+        let name_value = match meta {
+            NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("rename") => meta,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Unknown dom_id attribute, expected rename = \"...\"",
+                ))
+            }
+        };
+
+        match name_value.lit {
+            Lit::Str(lit) => rename = Some(lit.value()),
+            lit => return Err(syn::Error::new_spanned(lit, "Expected a string literal")),
+        }
+    }
+
+    Ok(rename)
+}
+
+fn to_kebab_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut kebab = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                kebab.push('-');
+            }
+        }
+
+        kebab.extend(c.to_lowercase());
+    }
+
+    kebab
+}
+
+#[proc_macro_derive(Route, attributes(route))]
+pub fn route_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    match expand_route(&ast) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn expand_route(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &ast.ident;
+
+    let variants = match &ast.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "Route can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut name_arms = vec![];
+    let mut path_arms = vec![];
+    let mut parsers = vec![];
+
+    for variant in variants {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let pattern = parse_route_attr(variant)?;
+
+        let fields: Vec<(Ident, bool)> = match &variant.fields {
+            Fields::Unit => vec![],
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| (field.ident.clone().unwrap(), is_option(&field.ty)))
+                .collect(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "Route variants must be unit variants or have named fields",
+                ))
+            }
+        };
+
+        for (field, _) in &fields {
+            let field_name = field.to_string();
+            if !pattern.params().any(|param| param == field_name)
+                && !pattern.query.contains(&field_name)
+            {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!("Field `{}` is not used in the route pattern", field_name),
+                ));
+            }
+        }
+
+        for param in pattern
+            .params()
+            .chain(pattern.query.iter().map(String::as_str))
+        {
+            if !fields.iter().any(|(field, _)| field == param) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    format!("Route pattern refers to unknown field `{}`", param),
+                ));
+            }
+        }
+
+        let is_optional = |param: &str| {
+            fields
+                .iter()
+                .any(|(field, optional)| field == param && *optional)
+        };
+
+        for (index, segment) in pattern.segments.iter().enumerate() {
+            match segment {
+                Segment::Param(param)
+                    if is_optional(param) && index + 1 != pattern.segments.len() =>
+                {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        format!(
+                            "Optional path parameter `{}` must be the last segment",
+                            param
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        let field_idents: Vec<&Ident> = fields.iter().map(|(field, _)| field).collect();
+        let destructure = match &variant.fields {
+            Fields::Unit => quote!(#name::#variant_ident),
+            _ => quote!(#name::#variant_ident { #(#field_idents),* }),
+        };
+
+        name_arms.push(quote! {
+            #name::#variant_ident { .. } => #variant_name.to_string(),
+        });
+
+        let segment_exprs = pattern.segments.iter().map(|segment| match segment {
+            Segment::Exact(s) => quote!(__segments.push(#s.to_string());),
+            Segment::Param(param) if is_optional(param) => {
+                let ident = Ident::new(param, proc_macro2::Span::call_site());
+                quote! {
+                    if let Some(__value) = #ident {
+                        __segments.push(__value.to_string());
+                    }
+                }
+            }
+            Segment::Param(param) => {
+                let ident = Ident::new(param, proc_macro2::Span::call_site());
+                quote!(__segments.push(#ident.to_string());)
+            }
+        });
+
+        let query_exprs = pattern.query.iter().map(|param| {
+            let ident = Ident::new(param, proc_macro2::Span::call_site());
+
+            if is_optional(param) {
+                quote!(let __url = __url.with_optional_query(#param, #ident.as_ref());)
+            } else {
+                quote!(let __url = __url.with_query(#param, #ident);)
+            }
+        });
+
+        path_arms.push(quote! {
+            #destructure => {
+                let mut __segments: Vec<String> = vec![];
+                #(#segment_exprs)*
+                let __url = poly::router::Url {
+                    segments: __segments,
+                    query: vec![],
+                    fragment: None,
+                };
+                #(#query_exprs)*
+                __url.to_string()
+            }
+        });
+
+        let segment_parsers = pattern.segments.iter().map(|segment| match segment {
+            Segment::Exact(s) => quote!(__parser.exact(#s)?;),
+            Segment::Param(param) if is_optional(param) => {
+                let ident = Ident::new(param, proc_macro2::Span::call_site());
+                quote!(let #ident = __parser.optional_param()?;)
+            }
+            Segment::Param(param) => {
+                let ident = Ident::new(param, proc_macro2::Span::call_site());
+                quote!(let #ident = __parser.param()?;)
+            }
+        });
+
+        let query_parsers = pattern.query.iter().map(|param| {
+            let ident = Ident::new(param, proc_macro2::Span::call_site());
+
+            if is_optional(param) {
+                quote!(let #ident = __url.query_param(#param);)
+            } else {
+                quote!(let #ident = __url.query_param(#param)?;)
+            }
+        });
+
+        parsers.push(quote! {
+            {
+                fn parse(__url: &poly::router::Url) -> Option<#name> {
+                    let mut __parser = __url.parser();
+                    #(#segment_parsers)*
+                    __parser.end()?;
+                    #(#query_parsers)*
+                    Some(#destructure)
+                }
+
+                if let Some(__route) = parse(__url) {
+                    return Some(__route);
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl poly::route::Route for #name {
+            fn name(&self) -> String {
+                match self {
+                    #(#name_arms)*
+                }
+            }
+
+            fn path(&self) -> String {
+                match self {
+                    #(#path_arms)*
+                }
+            }
+        }
+
+        impl poly::router::ParseRoute for #name {
+            fn from_url(__url: &poly::router::Url) -> Option<Self> {
+                #(#parsers)*
+                None
+            }
+        }
     })
 }
 
+enum Segment {
+    Exact(String),
+    Param(String),
+}
+
+struct RoutePattern {
+    segments: Vec<Segment>,
+    query: Vec<String>,
+}
+
+impl RoutePattern {
+    fn params(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Param(param) => Some(param.as_str()),
+            Segment::Exact(_) => None,
+        })
+    }
+}
+
+fn parse_route_attr(variant: &syn::Variant) -> syn::Result<RoutePattern> {
+    let attr = variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("route"))
+        .ok_or_else(|| syn::Error::new_spanned(variant, "Missing #[route(\"...\")] attribute"))?;
+
+    let lit: LitStr = attr.parse_args()?;
+    let value = lit.value();
+
+    if !value.starts_with('/') {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "Route pattern must start with /",
+        ));
+    }
+
+    let (path, query) = match value.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (value.as_str(), ""),
+    };
+
+    let segments = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(param) => Segment::Param(param.to_string()),
+            None => Segment::Exact(segment.to_string()),
+        })
+        .collect();
+
+    let query = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| param.to_string())
+        .collect();
+
+    Ok(RoutePattern { segments, query })
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+struct WasmPageArgs {
+    name: Ident,
+    stateful: bool,
+}
+
+impl Parse for WasmPageArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+
+        if input.is_empty() {
+            return Ok(WasmPageArgs {
+                name,
+                stateful: false,
+            });
+        }
+
+        input.parse::<Token![,]>()?;
+        let mode: Ident = input.parse()?;
+
+        if mode != "stateful" {
+            return Err(syn::Error::new_spanned(mode, "Expected `stateful`"));
+        }
+
+        Ok(WasmPageArgs {
+            name,
+            stateful: true,
+        })
+    }
+}
+
 #[proc_macro]
 pub fn impl_wasm_page(args: TokenStream) -> TokenStream {
-    let name_ident = parse_macro_input!(args as Ident);
+    let args = parse_macro_input!(args as WasmPageArgs);
+
+    if args.stateful {
+        impl_stateful_wasm_page(&args.name)
+    } else {
+        impl_stateless_wasm_page(&args.name)
+    }
+}
+
+fn impl_stateful_wasm_page(name_ident: &Ident) -> TokenStream {
+    TokenStream::from(quote!(
+        #[wasm_bindgen]
+        impl #name_ident {
+            #[wasm_bindgen(js_name = "id")]
+            pub fn id(&self) -> Result<String, JsValue> {
+                Ok(self.0.id().to_string())
+            }
+
+            #[wasm_bindgen(js_name = "init")]
+            pub fn init(&self) -> Result<JsValue, JsValue> {
+                wasm::stateful::init(&self.0, &self.1)
+            }
+
+            #[wasm_bindgen(js_name = "view")]
+            pub fn view(&self) -> Result<String, JsValue> {
+                wasm::stateful::view(&self.0, &self.1)
+            }
+
+            #[wasm_bindgen(js_name = "viewBody")]
+            pub fn view_body(&self) -> Result<String, JsValue> {
+                wasm::stateful::view_body(&self.0, &self.1)
+            }
 
+            #[wasm_bindgen(js_name = "viewBodyPatches")]
+            pub fn view_body_patches(&self) -> Result<JsValue, JsValue> {
+                wasm::stateful::view_body_patches(&self.0, &self.1)
+            }
+
+            #[wasm_bindgen(js_name = "viewWithState")]
+            pub fn view_with_state(&self) -> Result<String, JsValue> {
+                wasm::stateful::view_with_state(&self.0, &self.1)
+            }
+
+            #[wasm_bindgen(js_name = "getSubscriptions")]
+            pub fn get_subscriptions(&self) -> Result<JsValue, JsValue> {
+                wasm::stateful::get_subscriptions(&self.0, &self.1)
+            }
+
+            #[wasm_bindgen(js_name = "update")]
+            pub fn update(&self, js_msg: &JsValue) -> Result<JsValue, JsValue> {
+                wasm::stateful::update(&self.0, &self.1, js_msg)
+            }
+
+            #[wasm_bindgen(js_name = "updateFromJs")]
+            pub fn update_from_js(&self, js_msg: &JsValue) -> Result<JsValue, JsValue> {
+                wasm::stateful::update_from_js(&self.0, &self.1, js_msg)
+            }
+
+            #[wasm_bindgen(js_name = "getModel")]
+            pub fn get_model(&self) -> Result<JsValue, JsValue> {
+                wasm::stateful::get_model(&self.0, &self.1)
+            }
+
+            #[wasm_bindgen(js_name = "setModel")]
+            pub fn set_model(&self, js_model: &JsValue) -> Result<(), JsValue> {
+                wasm::stateful::set_model(&self.0, &self.1, js_model)
+            }
+        }
+    ))
+}
+
+fn impl_stateless_wasm_page(name_ident: &Ident) -> TokenStream {
     TokenStream::from(quote!(
         #[wasm_bindgen]
         impl #name_ident {
@@ -41,6 +584,16 @@ pub fn impl_wasm_page(args: TokenStream) -> TokenStream {
                 wasm::view_body(&self.0, js_model)
             }
 
+            #[wasm_bindgen(js_name = "viewWithState")]
+            pub fn view_with_state(&self, js_model: &JsValue) -> Result<String, JsValue> {
+                wasm::view_with_state(&self.0, js_model)
+            }
+
+            #[wasm_bindgen(js_name = "initViewWithState")]
+            pub fn init_view_with_state(&self, include_effects: bool) -> Result<String, JsValue> {
+                wasm::init_view_with_state(&self.0, include_effects)
+            }
+
             #[wasm_bindgen(js_name = "getSubscriptions")]
             pub fn get_subscriptions(&self, js_model: &JsValue) -> Result<JsValue, JsValue> {
                 wasm::get_subscriptions(&self.0, js_model)
@@ -58,3 +611,109 @@ pub fn impl_wasm_page(args: TokenStream) -> TokenStream {
         }
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dom_id_error(input: DeriveInput) -> String {
+        expand_dom_id(&input).unwrap_err().to_string()
+    }
+
+    fn route_error(input: DeriveInput) -> String {
+        expand_route(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn dom_id_kebab_case() {
+        assert_eq!(to_kebab_case("SearchInput"), "search-input");
+        assert_eq!(to_kebab_case("HTMLEditor"), "html-editor");
+        assert_eq!(to_kebab_case("Row2Column"), "row2-column");
+        assert_eq!(to_kebab_case("Id"), "id");
+    }
+
+    #[test]
+    fn dom_id_rejects_duplicate_ids() {
+        let input = syn::parse_quote! {
+            enum Id {
+                SearchInput,
+                #[dom_id(rename = "search-input")]
+                Search,
+            }
+        };
+
+        assert_eq!(dom_id_error(input), "Duplicate dom id `search-input`");
+    }
+
+    #[test]
+    fn dom_id_rejects_unknown_container_attribute() {
+        let input = syn::parse_quote! {
+            #[dom_id(display)]
+            enum Id {
+                Search,
+            }
+        };
+
+        assert_eq!(
+            dom_id_error(input),
+            "Unknown dom_id attribute, expected skip_display"
+        );
+    }
+
+    #[test]
+    fn route_rejects_optional_path_param_before_last_segment() {
+        let input = syn::parse_quote! {
+            enum AppRoute {
+                #[route("/files/:version/:name")]
+                File { version: Option<String>, name: String },
+            }
+        };
+
+        assert_eq!(
+            route_error(input),
+            "Optional path parameter `version` must be the last segment"
+        );
+    }
+
+    #[test]
+    fn route_rejects_unused_fields() {
+        let input = syn::parse_quote! {
+            enum AppRoute {
+                #[route("/posts")]
+                Post { id: u32 },
+            }
+        };
+
+        assert_eq!(
+            route_error(input),
+            "Field `id` is not used in the route pattern"
+        );
+    }
+
+    #[test]
+    fn route_rejects_unknown_params() {
+        let input = syn::parse_quote! {
+            enum AppRoute {
+                #[route("/posts/:id")]
+                Post,
+            }
+        };
+
+        assert_eq!(
+            route_error(input),
+            "Route pattern refers to unknown field `id`"
+        );
+    }
+
+    #[test]
+    fn route_requires_leading_slash() {
+        let input = syn::parse_quote! {
+            enum AppRoute {
+                #[route("posts")]
+                Posts,
+            }
+        };
+
+        assert_eq!(route_error(input), "Route pattern must start with /");
+    }
+}